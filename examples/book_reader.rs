@@ -1,6 +1,6 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    MouseEvent, MouseKind, Style, run,
+    Application, Color, Command, Constraint, Event, Frame, KeyCode, Layout, Modifier, MouseEvent,
+    MouseKind, Style, run,
     widgets::{Block, Borders, Scrollable, Text},
 };
 
@@ -99,15 +99,12 @@ impl Application for BookReader {
     }
 
     fn draw(&self, frame: &mut Frame) {
-        let [header, body, footer] = Layout::new(
-            Direction::Vertical,
-            vec![
-                Constraint::Length(4),
-                Constraint::Fill,
-                Constraint::Length(1),
-            ],
-        )
-        .split_to(frame.area());
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Fill,
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
 
         // --- Header ---
         let header_block = Block::new()
@@ -1,6 +1,5 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Style, run,
+    Application, Color, Command, Constraint, Event, Frame, KeyCode, Layout, Modifier, Style, run,
     widgets::{Block, Borders, Text},
 };
 
@@ -39,31 +38,25 @@ impl Application for Counter {
     }
 
     fn draw(&self, frame: &mut Frame) {
-        let [header, body, footer] = Layout::new(
-            Direction::Vertical,
-            vec![
-                Constraint::Length(1),
-                Constraint::Fill,
-                Constraint::Length(1),
-            ],
-        )
-        .split_to(frame.area());
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill,
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
 
-        frame.render_widget(
-            Text::new("Counter Example").style(Style::new().modifier(Modifier::BOLD)),
-            header,
-        );
+        frame.render_widget(Text::new("Counter Example").style(Style::bold()), header);
 
         let block = Block::new()
             .borders(Borders::ALL)
             .title("Hello")
             .style(Style::new().fg(Color::Magenta))
-            .title_style(Style::new().fg(Color::Green).modifier(Modifier::BOLD));
-
-        let inner_area = block.inner(body);
-        frame.render_widget(block, body);
+            .title_style(Style::fg_color(Color::Green).modifier(Modifier::BOLD));
 
-        frame.render_widget(Text::new(format!("Count: {}", self.value)), inner_area);
+        frame.render_widget(
+            block.wrap(Text::new(format!("Count: {}", self.value))),
+            body,
+        );
 
         frame.render_widget(
             Text::new("Press +/-, q to quit.").style(Style::new().fg(Color::Rgb(128, 128, 128))),
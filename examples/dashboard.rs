@@ -1,11 +1,16 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Style, run,
-    widgets::{Block, Borders, Text},
+    Application, Color, Command, Constraint, Event, Frame, KeyCode, Layout, Modifier, Rect, Style,
+    run,
+    widgets::{Block, Borders, Sparkline, Text},
 };
 
 struct State;
 
+/// Recent CPU usage samples, in percent.
+const CPU_HISTORY: [u64; 24] = [
+    12, 18, 25, 22, 30, 41, 38, 35, 44, 52, 47, 40, 36, 42, 55, 61, 58, 49, 45, 43, 39, 41, 44, 42,
+];
+
 #[derive(PartialEq)]
 enum Action {
     Quit,
@@ -32,43 +37,32 @@ impl Application for State {
     }
 
     fn draw(&self, frame: &mut Frame) {
-        let [header_area, body_area, footer_area] = Layout::new(
-            Direction::Vertical,
-            vec![
-                Constraint::Length(3),
-                Constraint::Fill,
-                Constraint::Length(1),
-            ],
-        )
-        .split_to(frame.area());
+        let [header_area, body_area, footer_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill,
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
 
         // --- HEADER ---
-        let header_block = Block::new()
-            .borders(Borders::BOTTOM)
-            .padding(0)
-            .style(Style::new().fg(Color::Rgb(0, 122, 204)));
-
-        let header_inner = header_block.inner(header_area);
-        frame.render_widget(header_block, header_area);
-
         let header_title = Text::new(" 🚀 BRIKS DASHBOARD v0.1.0 ").style(
             Style::new()
                 .fg(Color::White)
                 .bg(Color::Rgb(0, 122, 204))
                 .modifier(Modifier::BOLD),
         );
-        frame.render_widget(header_title, header_inner);
+        let header = Block::new()
+            .borders(Borders::BOTTOM)
+            .padding(0)
+            .style(Style::new().fg(Color::Rgb(0, 122, 204)))
+            .wrap(header_title);
+        frame.render_widget(header, header_area);
 
         // --- BODY ---
-        let [sidebar_area, _, content_area] = Layout::new(
-            Direction::Horizontal,
-            vec![
-                Constraint::Ratio(1, 4),
-                Constraint::Length(1),
-                Constraint::Fill,
-            ],
-        )
-        .split_to(body_area);
+        let [sidebar_area, content_area] =
+            Layout::horizontal([Constraint::Ratio(1, 4), Constraint::Fill])
+                .spacing(1)
+                .areas(body_area);
 
         // Sidebar
         let sidebar_block = Block::new()
@@ -103,6 +97,17 @@ impl Application for State {
             f.write_str(0, 2, "Memory:    [||||||----] 64%");
             f.write_str(0, 4, "Disk I/O:  Stable");
         });
+        frame.render_widget(
+            Sparkline::new(&CPU_HISTORY)
+                .max(100)
+                .style(Style::new().fg(Color::Green)),
+            Rect::new(
+                content_inner.x + 11,
+                content_inner.y + 1,
+                content_inner.width.saturating_sub(11),
+                1,
+            ),
+        );
 
         // --- FOOTER ---
         let footer_text = Text::new(" Q: Quit | S: Save | R: Refresh ")
@@ -1,7 +1,7 @@
 use phosphor::{
-    Application, Color, Command, Constraint, Direction, Event, Frame, KeyCode, Layout, Modifier,
-    Rect, Style, run,
-    widgets::{Block, Borders, Text},
+    Application, Color, Command, Constraint, Event, Frame, KeyCode, Layout, Modifier, Rect, Style,
+    run,
+    widgets::{Block, Borders, Paragraph, Text},
 };
 
 struct ModalDemo {
@@ -40,7 +40,7 @@ impl Application for ModalDemo {
             .style(Style::new().fg(Color::Blue));
         frame.render_widget(bg_block, area);
 
-        let text = Text::new(
+        let text = Paragraph::new(
             "Press 'm' to toggle the modal.\nPress 'q' to quit.\n\n".to_string()
                 + &"Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(10),
         );
@@ -50,19 +50,13 @@ impl Application for ModalDemo {
         if self.show_modal {
             let modal_area = centered_rect(60, 20, area);
 
-            // Clear the modal area (simulate a solid background)
-            // We do this by drawing a block with a solid background style?
-            // Or just spaces. For now, the Block borders will define it.
-
             let modal = Block::new()
                 .borders(Borders::ALL)
                 .title(" Modal ")
-                .style(Style::new().fg(Color::Yellow).bg(Color::Black)) // Explicit BG helps
-                .title_style(Style::new().fg(Color::Red).modifier(Modifier::BOLD));
-
-            // Clear the area behind the modal to hide the text
-            // We can do this by writing spaces manually or adding a Clear widget later.
-            // For now, let's just rely on the Block.
+                .style(Style::new().fg(Color::Yellow).bg(Color::Black))
+                .title_style(Style::new().fg(Color::Red).modifier(Modifier::BOLD))
+                // Cover the text behind the modal so it doesn't show through.
+                .fill(true);
 
             frame.render_widget(modal, modal_area);
 
@@ -91,29 +85,21 @@ impl Application for ModalDemo {
 
 /// Helper function to center a rect using Layout
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::new(
-        Direction::Vertical,
-        vec![
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ],
-    )
-    .split(r);
-
-    let vertical_slice = popup_layout[1];
-
-    let horizontal_layout = Layout::new(
-        Direction::Horizontal,
-        vec![
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ],
-    )
-    .split(vertical_slice);
-
-    horizontal_layout[1]
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill,
+        Constraint::Percentage(percent_y),
+        Constraint::Fill,
+    ])
+    .areas(r);
+
+    let [_, center, _] = Layout::horizontal([
+        Constraint::Fill,
+        Constraint::Percentage(percent_x),
+        Constraint::Fill,
+    ])
+    .areas(row);
+
+    center
 }
 
 fn main() -> std::io::Result<()> {
@@ -50,15 +50,9 @@ impl Application for MouseDemo {
         frame.render_widget(Text::new(info), Rect::new(2, 2, area.width - 4, 5));
 
         if let Some((x, y)) = self.click_pos {
-            // Draw a target at the click position
-            // We need to be careful not to draw outside the frame
-            if x < area.width && y < area.height {
-                frame.write_str_with_style(
-                    x,
-                    y,
-                    "X",
-                    Style::new().fg(Color::Red).modifier(Modifier::BOLD),
-                );
+            // Draw a target at the click position, if it's inside the frame
+            if area.contains(x, y) {
+                frame.write_str_with_style(x, y, "X", Style::error());
             }
         }
     }
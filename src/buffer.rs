@@ -4,16 +4,57 @@
 //! the framework can perform "diff-rendering," only updating the parts of the
 //! terminal that have actually changed.
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
 use crate::{Rect, Style};
 
 /// A single character on the screen with its associated style.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Cell {
     /// The character to display in this cell.
     pub symbol: char,
     pub style: Style,
 }
 
+/// The symbol of the cell covered by the right half of a double-width char.
+///
+/// The renderer skips these cells, since printing the wide char already
+/// filled them.
+pub const CONTINUATION: char = '\0';
+
+impl Cell {
+    /// Returns whether this cell is the right half of a double-width char.
+    pub fn is_continuation(&self) -> bool {
+        self.symbol == CONTINUATION
+    }
+}
+
+/// Returns the number of terminal columns `c` occupies: 2 for East Asian
+/// Wide/Fullwidth characters and emoji, 1 otherwise.
+pub fn char_width(c: char) -> u16 {
+    match c as u32 {
+        0x1100..=0x115F
+        | 0x2329..=0x232A
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
 impl Default for Cell {
     /// Returns a cell containing a space character.
     fn default() -> Self {
@@ -32,8 +73,17 @@ pub struct Change {
     pub cell: Cell,
 }
 
+/// A run of consecutive changed cells on one row, from [`Buffer::diff_spans`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChangeSpan {
+    pub x: u16,
+    pub y: u16,
+    /// The new cells, left to right from `x`.
+    pub cells: Vec<Cell>,
+}
+
 /// A 2D grid of [`Cell`]s representing a terminal frame.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Buffer {
     /// The width of the buffer in columns.
     pub width: u16,
@@ -43,14 +93,65 @@ pub struct Buffer {
     pub content: Vec<Cell>,
 }
 
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            content: self.content.clone(),
+        }
+    }
+
+    /// Copies `source` into `self`, reusing the existing cell allocation when
+    /// it is large enough.
+    fn clone_from(&mut self, source: &Self) {
+        self.width = source.width;
+        self.height = source.height;
+        self.content.clone_from(&source.content);
+    }
+}
+
+impl fmt::Display for Buffer {
+    /// Writes the buffer's symbols, one line per row (see [`Buffer::to_text`]).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_text(false))
+    }
+}
+
 impl Buffer {
     /// Creates a new buffer of the given size, filled with default cells.
     pub fn new(width: u16, height: u16) -> Self {
         Self {
             width,
             height,
-            content: vec![Cell::default(); (width * height) as usize],
+            content: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    /// Resizes the buffer to `width` x `height`.
+    ///
+    /// Cells inside both the old and the new dimensions keep their content;
+    /// the rest of the grid is filled with default cells. A wide character
+    /// cut in half by the new right edge is blanked.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        if width == self.width && height == self.height {
+            return;
         }
+
+        let mut resized = Buffer::new(width, height);
+        let keep_width = width.min(self.width);
+        for y in 0..height.min(self.height) {
+            for x in 0..keep_width {
+                resized.set_cell(x, y, *self.get(x, y));
+            }
+            if keep_width > 0
+                && keep_width < self.width
+                && self.get(keep_width, y).is_continuation()
+            {
+                resized.set_cell(keep_width - 1, y, Cell::default());
+            }
+        }
+        *self = resized;
     }
 
     /// Returns a reference to the cell at the given coordinates.
@@ -67,15 +168,37 @@ impl Buffer {
             .expect("No cell found at {x}:{y}")
     }
 
+    /// Returns the cell at the given coordinates, or `None` if they are out
+    /// of bounds.
+    pub fn get_opt(&self, x: u16, y: u16) -> Option<&Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.content.get(self.index(x, y))
+    }
+
+    /// Returns a mutable reference to the cell at the given coordinates, or
+    /// `None` if they are out of bounds.
+    pub fn get_mut(&mut self, x: u16, y: u16) -> Option<&mut Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let index = self.index(x, y);
+        self.content.get_mut(index)
+    }
+
     /// Sets the character at the given coordinates.
     ///
+    /// A double-width character also claims the next cell as a
+    /// [`CONTINUATION`]; see [`set_with_style`](Self::set_with_style).
+    ///
     /// Does nothing if the coordinates are out of bounds.
     pub fn set(&mut self, x: u16, y: u16, symbol: char) {
         if x >= self.width || y >= self.height {
             return;
         }
-        let idx = self.index(x, y);
-        self.content[idx].symbol = symbol;
+        let style = self.content[self.index(x, y)].style;
+        self.set_with_style(x, y, symbol, style);
     }
 
     /// Sets the style of the cell at the given coordinates.
@@ -91,14 +214,41 @@ impl Buffer {
 
     /// Sets both the character and the style at the given coordinates.
     ///
+    /// A double-width character also claims the next cell as a
+    /// [`CONTINUATION`]; one that doesn't fit before the right edge is drawn
+    /// as a space. Overwriting either half of an existing wide character
+    /// blanks its other half.
+    ///
     /// Does nothing if the coordinates are out of bounds.
     pub fn set_with_style(&mut self, x: u16, y: u16, symbol: char, style: Style) {
         if x >= self.width || y >= self.height {
             return;
         }
+
+        let (symbol, width) = match char_width(symbol) {
+            2 if x + 1 >= self.width => (' ', 1),
+            width => (symbol, width),
+        };
+
+        // Don't leave half of a wide char we're overwriting on screen.
+        if x > 0 && self.get(x, y).is_continuation() {
+            let idx = self.index(x - 1, y);
+            self.content[idx].symbol = ' ';
+        }
+        let end = x + width;
+        if end < self.width && self.get(end, y).is_continuation() {
+            let idx = self.index(end, y);
+            self.content[idx].symbol = ' ';
+        }
+
         let idx = self.index(x, y);
-        self.content[idx].symbol = symbol;
-        self.content[idx].style = style;
+        self.content[idx] = Cell { symbol, style };
+        if width == 2 {
+            self.content[idx + 1] = Cell {
+                symbol: CONTINUATION,
+                style,
+            };
+        }
     }
 
     /// Sets the cell at the given coordinates.
@@ -112,9 +262,91 @@ impl Buffer {
         self.content[idx] = cell;
     }
 
+    /// Sets every cell of `rect` to `symbol` with `style`.
+    ///
+    /// The parts of `rect` outside the buffer are ignored.
+    pub fn fill(&mut self, rect: Rect, symbol: char, style: Style) {
+        let right = rect.x.saturating_add(rect.width).min(self.width);
+        let bottom = rect.y.saturating_add(rect.height).min(self.height);
+        for y in rect.y..bottom {
+            for x in rect.x..right {
+                self.set_with_style(x, y, symbol, style);
+            }
+        }
+    }
+
+    /// Writes `text` along row `y` starting at column `x`, with `style`.
+    ///
+    /// Double-width characters advance two columns. Writing stops at the
+    /// right edge of the buffer.
+    pub fn set_string(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        if y >= self.height {
+            return;
+        }
+        let mut col = x;
+        for c in text.chars() {
+            if col >= self.width {
+                break;
+            }
+            self.set_with_style(col, y, c, style);
+            col = col.saturating_add(char_width(c));
+        }
+    }
+
+    /// Returns the cells of row `y`, skipping the continuation cells of
+    /// wide characters.
+    fn row_cells(&self, y: u16) -> impl Iterator<Item = &Cell> {
+        let start = y as usize * self.width as usize;
+        self.content[start..start + self.width as usize]
+            .iter()
+            .filter(|cell| !cell.is_continuation())
+    }
+
+    /// Renders the buffer as plain text, one line per row, for snapshot tests.
+    ///
+    /// Styles are dropped. With `trim_trailing`, spaces at the end of each row
+    /// are removed. The [`Display`](fmt::Display) impl is the untrimmed form.
+    pub fn to_text(&self, trim_trailing: bool) -> String {
+        let lines: Vec<String> = (0..self.height)
+            .map(|y| {
+                let line: String = self.row_cells(y).map(|cell| cell.symbol).collect();
+                if trim_trailing {
+                    line.trim_end_matches(' ').to_string()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Like [`to_text`](Self::to_text), but keeps the styles as ANSI SGR
+    /// sequences (see [`Style::to_ansi`]).
+    ///
+    /// Each row starts with its first cell's style, switches whenever the
+    /// style changes and ends with a reset (`ESC [ 0 m`).
+    pub fn to_ansi_string(&self) -> String {
+        let mut out = String::new();
+        for y in 0..self.height {
+            if y > 0 {
+                out.push('\n');
+            }
+            let mut current = None;
+            for cell in self.row_cells(y) {
+                if current != Some(cell.style) {
+                    out.push_str(&cell.style.to_ansi());
+                    current = Some(cell.style);
+                }
+                out.push(cell.symbol);
+            }
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+
     /// Helper to convert 2D coordinates to a 1D index.
     fn index(&self, x: u16, y: u16) -> usize {
-        ((y * self.width) + x) as usize
+        y as usize * self.width as usize + x as usize
     }
 
     /// Compares this buffer with another and returns the list of changed cells.
@@ -129,8 +361,8 @@ impl Buffer {
                 .iter()
                 .enumerate()
                 .map(|(i, cell)| Change {
-                    x: (i as u16) % self.width,
-                    y: (i as u16) / self.width,
+                    x: (i % self.width as usize) as u16,
+                    y: (i / self.width as usize) as u16,
                     cell: *cell,
                 })
                 .collect();
@@ -140,8 +372,8 @@ impl Buffer {
             {
                 if new_cell != old_cell {
                     changes.push(Change {
-                        x: (i as u16) % self.width,
-                        y: (i as u16) / self.width,
+                        x: (i % self.width as usize) as u16,
+                        y: (i / self.width as usize) as u16,
                         cell: *new_cell,
                     })
                 }
@@ -151,6 +383,51 @@ impl Buffer {
         changes
     }
 
+    /// Like [`diff`](Self::diff), but groups changed cells that follow each
+    /// other on a row into [`ChangeSpan`]s.
+    ///
+    /// With `same_style`, a span also ends where the style changes, so each
+    /// span can be written with a single SGR sequence. Continuation cells of
+    /// wide chars always stay in the span of their char.
+    pub fn diff_spans(&self, other: &Buffer, same_style: bool) -> Vec<ChangeSpan> {
+        let mut spans: Vec<ChangeSpan> = Vec::new();
+        for change in self.diff(other) {
+            if let Some(span) = spans.last_mut()
+                && span.y == change.y
+                && span.x + span.cells.len() as u16 == change.x
+                && (!same_style
+                    || change.cell.is_continuation()
+                    || span
+                        .cells
+                        .iter()
+                        .rfind(|cell| !cell.is_continuation())
+                        .is_none_or(|cell| cell.style == change.cell.style))
+            {
+                span.cells.push(change.cell);
+                continue;
+            }
+            spans.push(ChangeSpan {
+                x: change.x,
+                y: change.y,
+                cells: vec![change.cell],
+            });
+        }
+        spans
+    }
+
+    /// Returns a 64-bit FNV-1a hash of the buffer's dimensions and cells.
+    ///
+    /// This allows cheap "did anything change?" checks between frames. Equal
+    /// buffers always hash equal; a matching hash should still be confirmed with
+    /// `==` since collisions are possible.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.width.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        self.content.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Copies a rectangular area from another buffer into this one.
     ///
     /// This is useful for scrolling or compositing multiple buffers.
@@ -164,6 +441,28 @@ impl Buffer {
     }
 }
 
+/// A minimal FNV-1a hasher, used for [`Buffer::content_hash`].
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +549,226 @@ mod tests {
         assert_eq!(changes.len(), 1);
         assert_eq!(changes[0].cell.style.foreground, Some(Color::Red));
     }
+
+    #[test]
+    fn test_buffer_set_preserves_style() {
+        use crate::style::Color;
+
+        let mut buf = Buffer::new(2, 1);
+        buf.set_with_style(0, 0, 'A', Style::new().fg(Color::Blue));
+        buf.set(0, 0, 'B');
+
+        assert_eq!(buf.get(0, 0).symbol, 'B');
+        assert_eq!(buf.get(0, 0).style.foreground, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_buffer_wide_char_continuation() {
+        let mut buf = Buffer::new(4, 1);
+        buf.set(0, 0, '世');
+
+        assert_eq!(buf.get(0, 0).symbol, '世');
+        assert!(buf.get(1, 0).is_continuation());
+        assert!(!buf.get(2, 0).is_continuation());
+
+        // A wide char with no room for its right half becomes a space.
+        buf.set(3, 0, '界');
+        assert_eq!(buf.get(3, 0).symbol, ' ');
+    }
+
+    #[test]
+    fn test_buffer_overwrite_half_of_wide_char() {
+        let mut buf = Buffer::new(4, 1);
+        buf.set(0, 0, '世');
+        buf.set(1, 0, 'x');
+        assert_eq!(buf.get(0, 0).symbol, ' ');
+        assert_eq!(buf.get(1, 0).symbol, 'x');
+
+        buf.set(2, 0, '界');
+        buf.set(2, 0, 'y');
+        assert_eq!(buf.get(3, 0).symbol, ' ');
+    }
+
+    #[test]
+    fn test_buffer_fill_clips_to_bounds() {
+        use crate::style::Color;
+
+        let mut buffer = Buffer::new(4, 3);
+        let style = Style::new().bg(Color::Blue);
+        buffer.fill(Rect::new(2, 1, 10, 10), '#', style);
+
+        assert_eq!(buffer.get(1, 1).symbol, ' ');
+        assert_eq!(buffer.get(2, 0).symbol, ' ');
+        for (x, y) in [(2, 1), (3, 1), (2, 2), (3, 2)] {
+            assert_eq!(*buffer.get(x, y), Cell { symbol: '#', style });
+        }
+    }
+
+    #[test]
+    fn test_buffer_set_string_clips_at_right_edge() {
+        use crate::style::Color;
+
+        let mut buffer = Buffer::new(5, 2);
+        let style = Style::new().fg(Color::Red);
+        buffer.set_string(2, 1, "hello", style);
+
+        let row: String = (0..5).map(|x| buffer.get(x, 1).symbol).collect();
+        assert_eq!(row, "  hel");
+        assert_eq!(buffer.get(4, 1).style, style);
+        assert_eq!(buffer.get(1, 1).style, Style::default());
+
+        // Out-of-bounds rows are ignored.
+        buffer.set_string(0, 5, "x", style);
+    }
+
+    #[test]
+    fn test_buffer_resize_grow_keeps_content() {
+        let mut buffer = Buffer::new(2, 2);
+        buffer.set_string(0, 0, "ab", Style::default());
+        buffer.set_string(0, 1, "cd", Style::default());
+
+        buffer.resize(3, 3);
+
+        assert_eq!((buffer.width, buffer.height), (3, 3));
+        assert_eq!(buffer.content.len(), 9);
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        assert_eq!(buffer.get(1, 1).symbol, 'd');
+        assert_eq!(*buffer.get(2, 0), Cell::default());
+        assert_eq!(*buffer.get(0, 2), Cell::default());
+    }
+
+    #[test]
+    fn test_buffer_resize_shrink_drops_content() {
+        let mut buffer = Buffer::new(4, 3);
+        buffer.set_string(0, 0, "abcd", Style::default());
+        buffer.set_string(0, 2, "efgh", Style::default());
+        buffer.set_string(0, 1, "x\u{4E16}", Style::default());
+
+        buffer.resize(2, 2);
+
+        assert_eq!(buffer.content.len(), 4);
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        assert_eq!(buffer.get(1, 0).symbol, 'b');
+        // The wide char at column 1 lost its right half.
+        assert_eq!(buffer.get(0, 1).symbol, 'x');
+        assert_eq!(buffer.get(1, 1).symbol, ' ');
+    }
+
+    #[test]
+    fn test_buffer_to_text() {
+        let mut buffer = Buffer::new(4, 3);
+        buffer.set_string(0, 0, "ab", Style::default());
+        buffer.set_string(1, 2, "\u{4E16}d", Style::default());
+
+        assert_eq!(buffer.to_string(), "ab  \n    \n \u{4E16}d");
+        assert_eq!(buffer.to_text(true), "ab\n\n \u{4E16}d");
+    }
+
+    #[test]
+    fn test_buffer_to_ansi_string() {
+        use crate::style::Color;
+
+        let red = Style::new().fg(Color::Red);
+        let mut buffer = Buffer::new(3, 2);
+        buffer.set_string(0, 0, "ab", red);
+        buffer.set_string(0, 1, "cd", red);
+
+        let plain = Style::default().to_ansi();
+        let red = red.to_ansi();
+        assert_eq!(
+            buffer.to_ansi_string(),
+            format!("{red}ab{plain} \x1b[0m\n{red}cd{plain} \x1b[0m")
+        );
+    }
+
+    #[test]
+    fn test_buffer_content_hash() {
+        let mut a = Buffer::new(4, 2);
+        let mut b = Buffer::new(4, 2);
+        a.set(1, 1, 'X');
+        b.set(1, 1, 'X');
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        b.set(2, 1, 'Y');
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_buffer_content_hash_style_and_size() {
+        use crate::style::Color;
+
+        let a = Buffer::new(2, 2);
+        let mut b = Buffer::new(2, 2);
+        b.set_style(0, 0, Style::new().fg(Color::Red));
+        assert_ne!(a.content_hash(), b.content_hash());
+
+        // Same cell count, different shape
+        assert_ne!(
+            Buffer::new(4, 1).content_hash(),
+            Buffer::new(1, 4).content_hash()
+        );
+    }
+
+    #[test]
+    fn test_diff_spans_full_row_is_one_span() {
+        let prev = Buffer::new(5, 2);
+        let mut next = Buffer::new(5, 2);
+        next.set_string(0, 1, "hello", Style::default());
+
+        let spans = next.diff_spans(&prev, true);
+
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].x, spans[0].y), (0, 1));
+        let text: String = spans[0].cells.iter().map(|c| c.symbol).collect();
+        assert_eq!(text, "hello");
+    }
+
+    #[test]
+    fn test_diff_spans_split_on_gap_and_style() {
+        use crate::style::Color;
+
+        let prev = Buffer::new(6, 1);
+        let mut next = Buffer::new(6, 1);
+        next.set_string(0, 0, "ab", Style::default());
+        next.set_string(3, 0, "cd", Style::default());
+
+        let spans = next.diff_spans(&prev, true);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[1].x, spans[1].cells.len()), (3, 2));
+
+        // A style change splits a span only when styles must match.
+        next.set_with_style(1, 0, 'b', Style::new().fg(Color::Red));
+        assert_eq!(next.diff_spans(&prev, true).len(), 3);
+        assert_eq!(next.diff_spans(&prev, false).len(), 2);
+    }
+
+    #[test]
+    fn test_buffer_get_opt_and_get_mut() {
+        let mut buf = Buffer::new(3, 2);
+        buf.set(2, 1, 'z');
+
+        assert_eq!(buf.get_opt(2, 1).map(|c| c.symbol), Some('z'));
+        assert_eq!(buf.get_opt(3, 1), None);
+        assert_eq!(buf.get_opt(0, 2), None);
+        assert!(buf.get_mut(3, 0).is_none());
+
+        buf.get_mut(1, 0).unwrap().symbol = 'm';
+        assert_eq!(buf.get(1, 0).symbol, 'm');
+    }
+
+    #[test]
+    fn test_buffer_larger_than_u16_cells() {
+        // 90,000 cells: indices past 65,535 must not wrap.
+        let mut buf = Buffer::new(300, 300);
+        buf.set(299, 299, 'z');
+        assert_eq!(buf.get(299, 299).symbol, 'z');
+
+        let changes = buf.diff(&Buffer::new(300, 300));
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].x, changes[0].y), (299, 299));
+
+        let all = buf.diff(&Buffer::new(0, 0));
+        assert_eq!(all.len(), 90_000);
+        assert_eq!((all[89_999].x, all[89_999].y), (299, 299));
+    }
 }
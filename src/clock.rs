@@ -0,0 +1,86 @@
+//! The `clock` module abstracts over time for the event loop.
+//!
+//! Like [`System`](crate::terminal::System) for the terminal, the [`Clock`]
+//! trait is a seam that lets tests drive timers deterministically instead of
+//! waiting on the wall clock.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A source of time for the runtime.
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Blocks the current thread for the given duration.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The production [`Clock`], backed by [`Instant::now`] and [`thread::sleep`].
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mocks {
+    use super::*;
+    use std::cell::Cell;
+
+    /// A clock that only advances when `sleep` is called.
+    pub struct MockClock {
+        start: Instant,
+        elapsed: Cell<Duration>,
+    }
+
+    impl MockClock {
+        pub fn new() -> Self {
+            Self {
+                start: Instant::now(),
+                elapsed: Cell::new(Duration::ZERO),
+            }
+        }
+
+        pub fn start(&self) -> Instant {
+            self.start
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            self.elapsed.get()
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.start + self.elapsed.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.elapsed.set(self.elapsed.get() + duration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mocks::MockClock;
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_advances_on_sleep() {
+        let clock = MockClock::new();
+        let t0 = clock.now();
+
+        clock.sleep(Duration::from_millis(40));
+
+        assert_eq!(clock.now() - t0, Duration::from_millis(40));
+        assert_eq!(clock.elapsed(), Duration::from_millis(40));
+    }
+}
@@ -4,6 +4,8 @@
 //! for drawing text, shapes, and widgets without having to manipulate
 //! individual cells manually.
 
+use crate::buffer::char_width;
+use crate::widgets::{Block, StatefulWidget};
 use crate::{Buffer, Rect, Style, Widget};
 
 /// A high-level handle for drawing to a buffer.
@@ -11,6 +13,8 @@ pub struct Frame<'a> {
     buffer: &'a mut Buffer,
     area: Rect,
     current_style: Style,
+    ascii_only: bool,
+    cursor: Option<(u16, u16)>,
 }
 
 impl<'a> Frame<'a> {
@@ -20,9 +24,25 @@ impl<'a> Frame<'a> {
             buffer,
             area,
             current_style: Style::default(),
+            ascii_only: false,
+            cursor: None,
         }
     }
 
+    /// Sets whether widgets should restrict themselves to ASCII glyphs.
+    ///
+    /// See [`RunOptions::ascii_only`](crate::RunOptions::ascii_only).
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Returns `true` if widgets should avoid non-ASCII glyphs such as
+    /// box-drawing characters.
+    pub fn is_ascii_only(&self) -> bool {
+        self.ascii_only
+    }
+
     /// Returns the width of the frame.
     pub fn width(&self) -> u16 {
         self.area.width
@@ -57,21 +77,48 @@ impl<'a> Frame<'a> {
             buffer: self.buffer,
             current_style: self.current_style,
             area,
+            ascii_only: self.ascii_only,
+            cursor: None,
         };
         f(&mut sub_frame);
+        if sub_frame.cursor.is_some() {
+            self.cursor = sub_frame.cursor;
+        }
+    }
+
+    /// Asks for the terminal cursor to be shown at the given coordinates
+    /// once the frame is drawn, e.g. at the caret of a text input.
+    ///
+    /// Coordinates are relative to the frame. If no widget sets the cursor
+    /// during a frame, it stays hidden.
+    pub fn set_cursor(&mut self, x: u16, y: u16) {
+        self.cursor = Some((self.area.x + x, self.area.y + y));
+    }
+
+    /// Returns the cursor position requested with [`set_cursor`](Self::set_cursor),
+    /// in buffer coordinates.
+    pub fn cursor(&self) -> Option<(u16, u16)> {
+        self.cursor
     }
 
     /// Writes a string to the buffer starting at the given coordinates.
     ///
-    /// Text that exceeds the buffer width will be clipped.
+    /// Text is clipped to the frame's area, so a widget drawing into a
+    /// sub-frame can't spill into its neighbors.
     pub fn write_str(&mut self, x: u16, y: u16, text: &str) {
-        for (i, c) in text.chars().enumerate() {
-            self.buffer.set_with_style(
-                self.area.x + x + (i as u16),
-                self.area.y + y,
-                c,
-                self.current_style,
-            );
+        if y >= self.area.height {
+            return;
+        }
+        // Double-width characters advance by two columns.
+        let mut col = x;
+        for c in text.chars() {
+            let width = char_width(c);
+            if col.saturating_add(width) > self.area.width {
+                break;
+            }
+            self.buffer
+                .set_with_style(self.area.x + col, self.area.y + y, c, self.current_style);
+            col += width;
         }
     }
 
@@ -103,6 +150,67 @@ impl<'a> Frame<'a> {
         widget.render(area, self);
     }
 
+    /// Renders a [`StatefulWidget`] in the given area, passing it `state`.
+    ///
+    /// Since [`Application::draw`](crate::Application::draw) only gets
+    /// `&self`, keep the state in a `RefCell` in the model and pass
+    /// `&mut state.borrow_mut()`.
+    pub fn render_stateful_widget<W: StatefulWidget>(
+        &mut self,
+        widget: W,
+        area: Rect,
+        state: &mut W::State,
+    ) {
+        widget.render(area, self, state);
+    }
+
+    /// Renders a block into the given area and returns its inner area.
+    ///
+    /// This is shorthand for calling [`Block::inner`] before rendering the
+    /// block, for the common case of drawing content inside it.
+    pub fn render_block(&mut self, block: Block, area: Rect) -> Rect {
+        let inner = block.inner(area);
+        block.render(area, self);
+        inner
+    }
+
+    /// Copies the cells in `src` so its top-left corner lands at `dst`.
+    ///
+    /// Both are relative to the frame, and the copy is clipped to the frame.
+    /// Overlapping regions are handled, so this can shift already-drawn
+    /// content (e.g. scroll a viewport by one row). Source cells are left as
+    /// they were; the caller redraws the newly exposed area.
+    pub fn copy_region(&mut self, src: Rect, dst: (u16, u16)) {
+        let width = src
+            .width
+            .min(self.area.width.saturating_sub(src.x.max(dst.0)));
+        let height = src
+            .height
+            .min(self.area.height.saturating_sub(src.y.max(dst.1)));
+        let (ax, ay) = (self.area.x, self.area.y);
+
+        // Snapshot the source first so overlapping copies read original cells.
+        let mut cells = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = (ax + src.x + x, ay + src.y + y);
+                if sx < self.buffer.width && sy < self.buffer.height {
+                    cells.push(Some(*self.buffer.get(sx, sy)));
+                } else {
+                    cells.push(None);
+                }
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                if let Some(cell) = cells[y as usize * width as usize + x as usize] {
+                    self.buffer.set_cell(ax + dst.0 + x, ay + dst.1 + y, cell);
+                }
+            }
+        }
+    }
+
     /// Writes a string to the buffer starting at the given coordinates using a specific style.
     ///
     /// This is a convenience method that temporarily sets the style, writes the string,
@@ -119,7 +227,7 @@ mod tests {
     use super::*;
     use crate::Color;
     use crate::buffer::Buffer;
-    use crate::widgets::Text;
+    use crate::widgets::{Borders, Text};
 
     #[test]
     fn test_frame_render_widget() {
@@ -132,6 +240,21 @@ mod tests {
         assert_eq!(buffer.get(0, 0).symbol, 'W');
     }
 
+    #[test]
+    fn test_frame_render_block_returns_inner() {
+        let mut buffer = Buffer::new(10, 5);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 5));
+        let area = Rect::new(1, 1, 8, 4);
+        let expected = Block::new().borders(Borders::ALL).inner(area);
+
+        let inner = frame.render_block(Block::new().borders(Borders::ALL), area);
+
+        assert_eq!(inner, expected);
+        assert_eq!(inner, Rect::new(2, 2, 6, 2));
+        assert_eq!(buffer.get(1, 1).symbol, '\u{256D}');
+        assert_eq!(buffer.get(8, 4).symbol, '\u{256F}');
+    }
+
     #[test]
     fn test_frame_with_style_scoped() {
         let mut buffer = Buffer::new(10, 1);
@@ -169,6 +292,79 @@ mod tests {
         assert_eq!(buffer.get(0, 0).symbol, ' ');
     }
 
+    #[test]
+    fn test_frame_write_str_clipped_to_sub_frame() {
+        let mut buffer = Buffer::new(20, 20);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 20));
+
+        frame.render_area(Rect::new(10, 10, 5, 5), |f| {
+            f.write_str(0, 0, "a long string");
+            f.write_str(0, 5, "below");
+        });
+
+        assert_eq!(buffer.get(14, 10).symbol, 'n');
+        for x in 15..20 {
+            assert_eq!(buffer.get(x, 10).symbol, ' ');
+        }
+        assert_eq!(buffer.get(10, 15).symbol, ' ');
+    }
+
+    #[test]
+    fn test_frame_set_cursor_from_sub_frame() {
+        let mut buffer = Buffer::new(10, 10);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 10));
+        assert_eq!(frame.cursor(), None);
+
+        frame.render_area(Rect::new(2, 3, 5, 5), |f| f.set_cursor(1, 1));
+
+        assert_eq!(frame.cursor(), Some((3, 4)));
+    }
+
+    #[test]
+    fn test_frame_write_str_wide_chars() {
+        let mut buffer = Buffer::new(6, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 1));
+
+        frame.write_str(0, 0, "世a界b");
+
+        assert_eq!(buffer.get(0, 0).symbol, '世');
+        assert!(buffer.get(1, 0).is_continuation());
+        assert_eq!(buffer.get(2, 0).symbol, 'a');
+        assert_eq!(buffer.get(3, 0).symbol, '界');
+        assert!(buffer.get(4, 0).is_continuation());
+        assert_eq!(buffer.get(5, 0).symbol, 'b');
+    }
+
+    #[test]
+    fn test_frame_copy_region_down_one_row() {
+        let mut buffer = Buffer::new(3, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 3));
+        frame.write_str(0, 0, "abc");
+        frame.write_str(0, 1, "def");
+
+        frame.copy_region(Rect::new(0, 0, 3, 2), (0, 1));
+
+        assert_eq!(buffer.get(0, 1).symbol, 'a');
+        assert_eq!(buffer.get(2, 1).symbol, 'c');
+        assert_eq!(buffer.get(0, 2).symbol, 'd');
+        assert_eq!(buffer.get(2, 2).symbol, 'f');
+        // The source row is untouched, left for the caller to redraw.
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+    }
+
+    #[test]
+    fn test_frame_copy_region_clipped_to_frame() {
+        let mut buffer = Buffer::new(4, 4);
+        let mut frame = Frame::new(&mut buffer, Rect::new(1, 1, 2, 2));
+        frame.write_str(0, 0, "xy");
+
+        frame.copy_region(Rect::new(0, 0, 2, 1), (1, 1));
+
+        assert_eq!(buffer.get(2, 2).symbol, 'x');
+        // 'y' would land at (3, 2), outside the frame.
+        assert_eq!(buffer.get(3, 2).symbol, ' ');
+    }
+
     #[test]
     fn test_frame_styled_write_str() {
         let mut buffer = Buffer::new(10, 1);
@@ -208,4 +404,17 @@ mod tests {
         assert_eq!(buffer.get(2, 0).symbol, 'H');
         assert_eq!(buffer.get(4, 0).symbol, 'l');
     }
+
+    #[test]
+    fn test_frame_copy_region_larger_than_u16_cells() {
+        let mut buffer = Buffer::new(300, 301);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 300, 301));
+        frame.write_str(299, 299, "z");
+
+        // A 300x300 region holds 90,000 cells.
+        frame.copy_region(Rect::new(0, 0, 300, 300), (0, 1));
+
+        assert_eq!(buffer.get(299, 300).symbol, 'z');
+        assert_eq!(buffer.get(299, 299).symbol, ' ');
+    }
 }
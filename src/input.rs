@@ -10,7 +10,7 @@
 
 use std::collections::VecDeque;
 use std::fmt;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::terminal::Terminal;
 
@@ -23,9 +23,26 @@ pub enum Event {
     Mouse(MouseEvent),
     /// A terminal resize event (columns, rows).
     Resize(u16, u16),
+    /// No input arrived within [`RunOptions::tick_rate`](crate::RunOptions::tick_rate).
+    Tick,
+    /// The terminal's reply to a cursor position query (`ESC [ 6 n`), as
+    /// 0-based (column, row).
+    ///
+    /// A reply for the first row, `ESC [ 1 ; col R`, looks just like a
+    /// modified F3 key (`ESC [ 1 ; 5 R` is Ctrl+F3), so it is only decoded as
+    /// a report by a parser expecting one; see [`Parser::cursor_reports`].
+    CursorPosition(u16, u16),
+    /// The terminal window gained focus (`ESC [ I`).
+    FocusGained,
+    /// The terminal window lost focus (`ESC [ O`).
+    FocusLost,
 }
 
 /// Represents a mouse event.
+///
+/// Coordinates are 0-based screen cells, so they index the [`Buffer`](crate::Buffer)
+/// directly; use [`Rect::contains_mouse`](crate::Rect::contains_mouse) for
+/// hit-testing.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MouseEvent {
     /// The column (x) where the event occurred (0-based).
@@ -47,13 +64,69 @@ impl MouseEvent {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MouseKind {
     LeftClick,
+    /// A second left click at the same cell shortly after the first, as
+    /// detected by [`MouseState`].
+    DoubleClick,
     RightClick,
     MiddleClick,
     ScrollUp,
     ScrollDown,
+    /// The pointer moved with a button held (mode `?1002` or `?1003`).
+    Drag,
+    /// The pointer moved with no button held (mode `?1003`).
+    Moved,
     Other,
 }
 
+/// Promotes two quick left clicks at the same cell into a
+/// [`MouseKind::DoubleClick`].
+///
+/// The parser reports every click on its own; feed mouse events through
+/// [`track`](Self::track) to tell single clicks from double clicks. The run
+/// loop does this when [`RunOptions::double_click`](crate::RunOptions::double_click)
+/// is set.
+#[derive(Debug, Clone)]
+pub struct MouseState {
+    threshold: Duration,
+    /// When and where the last single left click happened.
+    last_click: Option<(Instant, u16, u16)>,
+}
+
+impl MouseState {
+    /// Creates a tracker that treats clicks at most `threshold` apart as a
+    /// double click.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last_click: None,
+        }
+    }
+
+    /// Returns `event`, turned into a double click if it's a left click at
+    /// the same cell as the previous one and no more than the threshold
+    /// after it.
+    ///
+    /// `now` is the time the event arrived. A third quick click starts over
+    /// as a single click.
+    pub fn track(&mut self, event: MouseEvent, now: Instant) -> MouseEvent {
+        if event.kind != MouseKind::LeftClick {
+            return event;
+        }
+        match self.last_click.take() {
+            Some((at, x, y))
+                if (x, y) == (event.x, event.y)
+                    && now.saturating_duration_since(at) <= self.threshold =>
+            {
+                MouseEvent::new(event.x, event.y, MouseKind::DoubleClick)
+            }
+            _ => {
+                self.last_click = Some((now, event.x, event.y));
+                event
+            }
+        }
+    }
+}
+
 /// Represents a specific key press, including modifiers.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeyEvent {
@@ -76,6 +149,49 @@ impl KeyEvent {
     pub fn with_modifiers(code: KeyCode, modifiers: KeyModifiers) -> Self {
         Self { code, modifiers }
     }
+
+    /// Creates a press of the character key `c` with no modifiers.
+    pub fn char(c: char) -> Self {
+        Self::new(KeyCode::Char(c))
+    }
+
+    /// Creates a press of the character key `c` with Ctrl held.
+    pub fn ctrl(c: char) -> Self {
+        Self::with_modifiers(KeyCode::Char(c), KeyModifiers::CTRL)
+    }
+
+    /// Creates a press of the character key `c` with Alt held.
+    pub fn alt(c: char) -> Self {
+        Self::with_modifiers(KeyCode::Char(c), KeyModifiers::ALT)
+    }
+}
+
+/// Formats the key as a shortcut, e.g. `Ctrl+C`, `Alt+Enter` or `F5`.
+///
+/// Modifiers come first, in the order Ctrl, Alt, Shift. A letter held with
+/// Ctrl or Alt is shown in upper case, as shortcuts are usually written.
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = [
+            (KeyModifiers::CTRL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+        ];
+        for (modifier, name) in names {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+
+        let chord = self.modifiers.contains(KeyModifiers::CTRL)
+            || self.modifiers.contains(KeyModifiers::ALT);
+        match self.code {
+            KeyCode::Char(c) if chord && c.is_ascii_lowercase() => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            ref code => write!(f, "{}", code),
+        }
+    }
 }
 
 /// Represents the key identifier.
@@ -85,7 +201,7 @@ pub enum KeyCode {
     Char(char),
     /// The Enter key (`\r`).
     Enter,
-    /// The Backspace key (`\x7f`).
+    /// The Backspace key (`\x7f`, or `\x08` on some terminals).
     Backspace,
     /// The Escape key (`\x1b`).
     Esc,
@@ -96,8 +212,10 @@ pub enum KeyCode {
     Down,
     /// The Tab key.
     Tab,
-    /// The Delete key.
+    /// The Delete key (`ESC [ 3 ~`).
     Delete,
+    /// The Insert key (`ESC [ 2 ~`).
+    Insert,
     /// Navigation keys.
     Home,
     End,
@@ -109,6 +227,34 @@ pub enum KeyCode {
     Null,
 }
 
+/// Formats the key's name, e.g. `Enter`, `PageUp` or `F5`. Characters are
+/// shown as themselves, except for the space bar, shown as `Space`.
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KeyCode::Char(' ') => "Space",
+            KeyCode::Char(c) => return write!(f, "{}", c),
+            KeyCode::F(n) => return write!(f, "F{}", n),
+            KeyCode::Enter => "Enter",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Esc => "Esc",
+            KeyCode::Left => "Left",
+            KeyCode::Right => "Right",
+            KeyCode::Up => "Up",
+            KeyCode::Down => "Down",
+            KeyCode::Tab => "Tab",
+            KeyCode::Delete => "Delete",
+            KeyCode::Insert => "Insert",
+            KeyCode::Home => "Home",
+            KeyCode::End => "End",
+            KeyCode::PageUp => "PageUp",
+            KeyCode::PageDown => "PageDown",
+            KeyCode::Null => "Null",
+        };
+        f.write_str(name)
+    }
+}
+
 /// A bitflag struct representing Shift, Ctrl, and Alt modifiers.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct KeyModifiers(u8);
@@ -166,6 +312,8 @@ impl std::ops::BitOr for KeyModifiers {
 /// event (like an arrow key) is split across multiple read operations.
 pub struct Parser {
     buffer: VecDeque<u8>,
+    backspace_sends_del: bool,
+    cursor_reports: bool,
 }
 
 impl Default for Parser {
@@ -179,9 +327,31 @@ impl Parser {
     pub fn new() -> Self {
         Self {
             buffer: VecDeque::new(),
+            backspace_sends_del: true,
+            cursor_reports: false,
         }
     }
 
+    /// Sets whether `ESC [ 1 ; m R` is a cursor position report for the first
+    /// row (see [`Event::CursorPosition`]) rather than a modified F3
+    /// (default: false).
+    ///
+    /// Other rows are always decoded as reports. Turn it on only while
+    /// waiting for the reply to a query.
+    pub fn cursor_reports(mut self, cursor_reports: bool) -> Self {
+        self.cursor_reports = cursor_reports;
+        self
+    }
+
+    /// Sets whether `0x7f` (DEL) is the Backspace key (default) or the Delete key.
+    ///
+    /// Most terminals send DEL for Backspace, but some send `0x08` instead and
+    /// use DEL for Delete.
+    pub fn backspace_sends_del(mut self, backspace_sends_del: bool) -> Self {
+        self.backspace_sends_del = backspace_sends_del;
+        self
+    }
+
     /// Parses a slice of bytes and appends them to the internal buffer,
     /// returning any complete events found.
     ///
@@ -201,6 +371,32 @@ impl Parser {
                     events.push(Event::Key(KeyEvent::new(KeyCode::Enter)));
                     self.buffer.pop_front();
                 }
+                b'\t' => {
+                    events.push(Event::Key(KeyEvent::new(KeyCode::Tab)));
+                    self.buffer.pop_front();
+                }
+                // Terminals that don't send DEL for Backspace send `^H`.
+                b'\x08' => {
+                    events.push(Event::Key(KeyEvent::new(KeyCode::Backspace)));
+                    self.buffer.pop_front();
+                }
+                b @ 0x01..=0x1a => {
+                    let letter = (b'a' + b - 1) as char;
+                    events.push(Event::Key(KeyEvent::with_modifiers(
+                        KeyCode::Char(letter),
+                        KeyModifiers::CTRL,
+                    )));
+                    self.buffer.pop_front();
+                }
+                b'\x7f' => {
+                    let code = if self.backspace_sends_del {
+                        KeyCode::Backspace
+                    } else {
+                        KeyCode::Delete
+                    };
+                    events.push(Event::Key(KeyEvent::new(code)));
+                    self.buffer.pop_front();
+                }
                 b'\x1b' => {
                     if self.buffer.len() == 1 {
                         break; // Incomplete, wait for more data
@@ -210,42 +406,81 @@ impl Parser {
                         break; // Incomplete CSI, wait for more data
                     }
 
-                    if self.buffer.len() >= 3 && self.buffer[1] == b'[' {
-                        match self.buffer[2] {
-                            b'A' => {
-                                events.push(Event::Key(KeyEvent::new(KeyCode::Up)));
-                                self.consume(3);
-                            }
-                            b'M' => {
-                                if self.buffer.len() < 6 {
-                                    break;
-                                }
-                                self.consume(3);
-
-                                let cb = self.buffer.pop_front().unwrap();
-                                let cx = self.buffer.pop_front().unwrap();
-                                let cy = self.buffer.pop_front().unwrap();
-
-                                let kind = match cb.saturating_sub(32) {
-                                    0 => MouseKind::LeftClick,
-                                    1 => MouseKind::MiddleClick,
-                                    2 => MouseKind::RightClick,
-                                    64 => MouseKind::ScrollUp,
-                                    65 => MouseKind::ScrollDown,
-                                    _ => MouseKind::Other,
-                                };
-
-                                events.push(Event::Mouse(MouseEvent::new(
-                                    (cx.saturating_sub(33)) as u16,
-                                    (cy.saturating_sub(33)) as u16,
-                                    kind,
+                    // SS3 sequences: `ESC O` followed by a single final byte.
+                    if self.buffer[1] == b'O' {
+                        if self.buffer.len() < 3 {
+                            break; // Incomplete SS3, wait for more data
+                        }
+                        // Application cursor mode sends arrows, Home and End
+                        // this way too.
+                        let code = match self.buffer[2] {
+                            b'P' => KeyCode::F(1),
+                            b'Q' => KeyCode::F(2),
+                            b'R' => KeyCode::F(3),
+                            b'S' => KeyCode::F(4),
+                            b'A' => KeyCode::Up,
+                            b'B' => KeyCode::Down,
+                            b'C' => KeyCode::Right,
+                            b'D' => KeyCode::Left,
+                            b'H' => KeyCode::Home,
+                            b'F' => KeyCode::End,
+                            _ => {
+                                // Not SS3 after all but Alt+Shift+O; the next
+                                // byte is a key of its own.
+                                events.push(Event::Key(KeyEvent::with_modifiers(
+                                    KeyCode::Char('O'),
+                                    KeyModifiers::ALT,
                                 )));
+                                self.consume(2);
+                                continue;
                             }
-                            _ => {
-                                events.push(Event::Key(KeyEvent::new(KeyCode::Esc)));
-                                self.buffer.pop_front();
+                        };
+                        events.push(Event::Key(KeyEvent::new(code)));
+                        self.consume(3);
+                        continue;
+                    }
+
+                    if self.buffer.len() >= 3 && self.buffer[1] == b'[' {
+                        if self.buffer[2] == b'M' {
+                            if self.buffer.len() < 6 {
+                                break;
                             }
+                            self.consume(3);
+
+                            let cb = self.buffer.pop_front().unwrap();
+                            let cx = self.buffer.pop_front().unwrap();
+                            let cy = self.buffer.pop_front().unwrap();
+
+                            events.push(Event::Mouse(MouseEvent::new(
+                                (cx.saturating_sub(33)) as u16,
+                                (cy.saturating_sub(33)) as u16,
+                                mouse_kind(cb.saturating_sub(32) as u16),
+                            )));
+                            continue;
                         }
+
+                        // Parameter and intermediate bytes run until a final
+                        // byte in `0x40..=0x7e`.
+                        let Some(end) = (2..self.buffer.len())
+                            .find(|&i| (0x40..=0x7e).contains(&self.buffer[i]))
+                        else {
+                            break; // Incomplete CSI, wait for more data
+                        };
+
+                        let params: Vec<u8> = self.buffer.range(2..end).copied().collect();
+                        let event = parse_csi(&params, self.buffer[end], self.cursor_reports);
+                        self.consume(end + 1);
+                        // Unknown sequences are dropped rather than leaking
+                        // their bytes as characters.
+                        events.extend(event);
+                    } else if (0x20..=0x7e).contains(&self.buffer[1]) {
+                        // Alt+key: terminals prefix the key's byte with ESC.
+                        let c = self.buffer[1] as char;
+                        events.push(Event::Key(KeyEvent::with_modifiers(
+                            KeyCode::Char(c),
+                            KeyModifiers::ALT,
+                        )));
+                        self.consume(2);
                     } else {
                         events.push(Event::Key(KeyEvent::new(KeyCode::Esc)));
                         self.buffer.pop_front();
@@ -306,6 +541,144 @@ impl Parser {
     }
 }
 
+/// Decodes a complete CSI sequence from its parameter bytes and final byte.
+///
+/// `ESC [ n ; m R` is a cursor position report if `cursor_reports` is set,
+/// and a modified F3 otherwise. Returns `None` for sequences that don't map
+/// to an [`Event`].
+fn parse_csi(params: &[u8], final_byte: u8, cursor_reports: bool) -> Option<Event> {
+    // Focus reports (mode 1004) have no parameters.
+    match (final_byte, params) {
+        (b'I', []) => return Some(Event::FocusGained),
+        (b'O', []) => return Some(Event::FocusLost),
+        (b'M' | b'm', [b'<', sgr @ ..]) => return parse_sgr_mouse(sgr, final_byte),
+        _ => {}
+    }
+
+    let params: Vec<u16> = std::str::from_utf8(params)
+        .ok()?
+        .split(';')
+        .map(|p| p.parse().unwrap_or(0))
+        .collect();
+
+    // Cursor position report: `ESC [ row ; col R`, 1-based. Modified F3 is
+    // `ESC [ 1 ; m R`, so a first-row report is only told apart from it when
+    // a reply is expected.
+    if final_byte == b'R' && params.len() == 2 && (params[0] != 1 || cursor_reports) {
+        let (row, col) = (params[0], params[1]);
+        return Some(Event::CursorPosition(
+            col.saturating_sub(1),
+            row.saturating_sub(1),
+        ));
+    }
+
+    let code = match final_byte {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        // Modified F1-F4, e.g. `ESC [ 1 ; 5 R` for Ctrl+F3.
+        b'P' => KeyCode::F(1),
+        b'Q' => KeyCode::F(2),
+        b'R' => KeyCode::F(3),
+        b'S' => KeyCode::F(4),
+        b'~' => tilde_key(params[0])?,
+        _ => return None,
+    };
+
+    // Modified keys carry a second parameter, e.g. `ESC [ 1 ; 5 C` for
+    // Ctrl+Right.
+    let modifiers = params
+        .get(1)
+        .copied()
+        .map_or(KeyModifiers::empty(), decode_modifiers);
+    Some(Event::Key(KeyEvent::with_modifiers(code, modifiers)))
+}
+
+/// Decodes an SGR mouse report, `ESC [ < b ; x ; y M` (press or motion) or
+/// `... m` (release), with 1-based coordinates.
+fn parse_sgr_mouse(params: &[u8], final_byte: u8) -> Option<Event> {
+    let params: Vec<u16> = std::str::from_utf8(params)
+        .ok()?
+        .split(';')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let [button, x, y] = params[..] else {
+        return None;
+    };
+    let kind = if final_byte == b'm' {
+        MouseKind::Other
+    } else {
+        mouse_kind(button)
+    };
+    Some(Event::Mouse(MouseEvent::new(
+        x.saturating_sub(1),
+        y.saturating_sub(1),
+        kind,
+    )))
+}
+
+/// Decodes the button code of a mouse report, shared by the X10 and SGR
+/// encodings.
+///
+/// The low two bits pick the button (3 is a release or no button), `32`
+/// flags motion and `64` the scroll wheel; the Shift, Alt and Ctrl bits (4, 8
+/// and 16) are ignored.
+fn mouse_kind(code: u16) -> MouseKind {
+    let code = code & !(4 | 8 | 16);
+    if code & 32 != 0 {
+        return if code & 3 == 3 {
+            MouseKind::Moved
+        } else {
+            MouseKind::Drag
+        };
+    }
+    match code {
+        0 => MouseKind::LeftClick,
+        1 => MouseKind::MiddleClick,
+        2 => MouseKind::RightClick,
+        64 => MouseKind::ScrollUp,
+        65 => MouseKind::ScrollDown,
+        _ => MouseKind::Other,
+    }
+}
+
+/// Decodes the key of a `ESC [ n ~` sequence from its parameter `n`.
+fn tilde_key(param: u16) -> Option<KeyCode> {
+    let code = match param {
+        1 | 7 => KeyCode::Home,
+        2 => KeyCode::Insert,
+        3 => KeyCode::Delete,
+        4 | 8 => KeyCode::End,
+        5 => KeyCode::PageUp,
+        6 => KeyCode::PageDown,
+        11..=15 => KeyCode::F((param - 10) as u8),
+        17..=21 => KeyCode::F((param - 11) as u8),
+        23 | 24 => KeyCode::F((param - 12) as u8),
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Decodes an xterm modifier parameter: `1 + mask`, where the mask bits are
+/// Shift (1), Alt (2) and Ctrl (4).
+fn decode_modifiers(param: u16) -> KeyModifiers {
+    let mask = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::empty();
+    if mask & 1 != 0 {
+        modifiers.insert(KeyModifiers::SHIFT);
+    }
+    if mask & 2 != 0 {
+        modifiers.insert(KeyModifiers::ALT);
+    }
+    if mask & 4 != 0 {
+        modifiers.insert(KeyModifiers::CTRL);
+    }
+    modifiers
+}
+
 fn utf8_char_width(first_byte: u8) -> usize {
     if first_byte & 0b10000000 == 0 {
         1
@@ -327,16 +700,43 @@ fn utf8_char_width(first_byte: u8) -> usize {
 /// by polling the terminal for a short duration.
 pub struct Input {
     parser: Parser,
+    buffer: Vec<u8>,
 }
 
+/// The default size of the buffer used for each read from the terminal.
+const DEFAULT_BUFFER_SIZE: usize = 1024;
+
 impl Input {
     /// Creates a new `Input` handler.
     pub fn new() -> Self {
         Self {
             parser: Parser::new(),
+            buffer: vec![0u8; DEFAULT_BUFFER_SIZE],
         }
     }
 
+    /// Sets how many bytes are requested from the terminal per read (default 1024).
+    ///
+    /// Larger buffers let big pastes be consumed in fewer read/poll round-trips.
+    /// Sequences split across reads are still reassembled by the parser. A size
+    /// of zero is treated as one.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer = vec![0u8; size.max(1)];
+        self
+    }
+
+    /// Sets how `ESC [ 1 ; m R` is decoded. See [`Parser::cursor_reports`].
+    pub fn cursor_reports(mut self, cursor_reports: bool) -> Self {
+        self.parser = self.parser.cursor_reports(cursor_reports);
+        self
+    }
+
+    /// Sets how `0x7f` is decoded. See [`Parser::backspace_sends_del`].
+    pub fn backspace_sends_del(mut self, backspace_sends_del: bool) -> Self {
+        self.parser = self.parser.backspace_sends_del(backspace_sends_del);
+        self
+    }
+
     /// Reads available bytes from the terminal and returns a vector of parsed events.
     ///
     /// This method will block until at least one byte is read from the terminal.
@@ -346,10 +746,10 @@ impl Input {
     /// # Errors
     /// Returns an error if the underlying terminal read or poll fails.
     pub fn read(&mut self, term: &Terminal) -> Vec<Event> {
-        let mut buf = [0u8; 1024];
+        let buf = &mut self.buffer;
         let mut events: Vec<Event> = Vec::new();
 
-        match term.read(&mut buf) {
+        match term.read(buf) {
             Ok(n) if n > 0 => {
                 events.extend(self.parser.parse(&buf[..n]));
             }
@@ -359,7 +759,7 @@ impl Input {
         while self.parser.has_pending_state() {
             match term.poll(Duration::from_millis(50)) {
                 Ok(true) => {
-                    if let Ok(n) = term.read(&mut buf) {
+                    if let Ok(n) = term.read(buf) {
                         events.extend(self.parser.parse(&buf[..n]));
                     }
                 }
@@ -372,6 +772,17 @@ impl Input {
 
         events
     }
+
+    /// Like [`read`](Self::read), but waits at most `timeout` for input to
+    /// arrive.
+    ///
+    /// Returns an empty list if the timeout expires with no input.
+    pub fn read_timeout(&mut self, term: &Terminal, timeout: Duration) -> Vec<Event> {
+        match term.poll(timeout) {
+            Ok(true) => self.read(term),
+            Ok(false) | Err(_) => Vec::new(),
+        }
+    }
 }
 
 impl Default for Input {
@@ -405,6 +816,245 @@ mod tests {
         assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Up))]);
     }
 
+    #[test]
+    fn test_parse_del_as_backspace() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x7f");
+        assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Backspace))]);
+    }
+
+    #[test]
+    fn test_parse_tab_and_backspace_bytes() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\t\x08\x7fa ");
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::new(KeyCode::Tab)),
+                Event::Key(KeyEvent::new(KeyCode::Backspace)),
+                Event::Key(KeyEvent::new(KeyCode::Backspace)),
+                Event::Key(KeyEvent::new(KeyCode::Char('a'))),
+                Event::Key(KeyEvent::new(KeyCode::Char(' '))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_del_as_delete() {
+        let mut parser = Parser::new().backspace_sends_del(false);
+        let events = parser.parse(b"\x7f");
+        assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Delete))]);
+    }
+
+    #[test]
+    fn test_parse_home_end_letter_form() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[H\x1b[F");
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::new(KeyCode::Home)),
+                Event::Key(KeyEvent::new(KeyCode::End)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_navigation_tilde_form() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[1~\x1b[4~\x1b[5~\x1b[6~");
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::new(KeyCode::Home)),
+                Event::Key(KeyEvent::new(KeyCode::End)),
+                Event::Key(KeyEvent::new(KeyCode::PageUp)),
+                Event::Key(KeyEvent::new(KeyCode::PageDown)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tilde_keys() {
+        let cases: &[(&[u8], KeyCode)] = &[
+            (b"\x1b[1~", KeyCode::Home),
+            (b"\x1b[2~", KeyCode::Insert),
+            (b"\x1b[3~", KeyCode::Delete),
+            (b"\x1b[4~", KeyCode::End),
+            (b"\x1b[5~", KeyCode::PageUp),
+            (b"\x1b[6~", KeyCode::PageDown),
+            (b"\x1b[7~", KeyCode::Home),
+            (b"\x1b[8~", KeyCode::End),
+        ];
+
+        for (bytes, code) in cases {
+            let mut parser = Parser::new();
+            let events = parser.parse(bytes);
+            assert_eq!(
+                events,
+                vec![Event::Key(KeyEvent::new(code.clone()))],
+                "{:?}",
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_split_tilde_sequence() {
+        let mut parser = Parser::new();
+        assert!(parser.parse(b"\x1b[5").is_empty());
+        assert!(parser.has_pending_state());
+
+        let events = parser.parse(b"~");
+        assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::PageUp))]);
+        assert!(!parser.has_pending_state());
+    }
+
+    #[test]
+    fn test_parse_function_keys() {
+        let cases: &[(&[u8], u8)] = &[
+            (b"\x1bOP", 1),
+            (b"\x1bOQ", 2),
+            (b"\x1bOR", 3),
+            (b"\x1bOS", 4),
+            (b"\x1b[11~", 1),
+            (b"\x1b[12~", 2),
+            (b"\x1b[13~", 3),
+            (b"\x1b[14~", 4),
+            (b"\x1b[15~", 5),
+            (b"\x1b[17~", 6),
+            (b"\x1b[18~", 7),
+            (b"\x1b[19~", 8),
+            (b"\x1b[20~", 9),
+            (b"\x1b[21~", 10),
+            (b"\x1b[23~", 11),
+            (b"\x1b[24~", 12),
+        ];
+
+        for (bytes, n) in cases {
+            let mut parser = Parser::new();
+            let events = parser.parse(bytes);
+            assert_eq!(
+                events,
+                vec![Event::Key(KeyEvent::new(KeyCode::F(*n)))],
+                "{:?}",
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_split_ss3_waits() {
+        let mut parser = Parser::new();
+        assert!(parser.parse(b"\x1bO").is_empty());
+
+        let events = parser.parse(b"P");
+        assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::F(1)))]);
+    }
+
+    #[test]
+    fn test_parse_ss3_cursor_keys() {
+        let cases = [
+            (b"\x1bOA", KeyCode::Up),
+            (b"\x1bOB", KeyCode::Down),
+            (b"\x1bOC", KeyCode::Right),
+            (b"\x1bOD", KeyCode::Left),
+            (b"\x1bOH", KeyCode::Home),
+            (b"\x1bOF", KeyCode::End),
+        ];
+        for (bytes, code) in cases {
+            let mut parser = Parser::new();
+            assert_eq!(parser.parse(bytes), vec![Event::Key(KeyEvent::new(code))]);
+        }
+    }
+
+    #[test]
+    fn test_parse_alt_shift_o_keeps_next_key() {
+        let mut parser = Parser::new();
+        assert_eq!(
+            parser.parse(b"\x1bOx"),
+            vec![
+                Event::Key(KeyEvent::alt('O')),
+                Event::Key(KeyEvent::char('x')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ctrl_keys() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x01\x03\x1a");
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::with_modifiers(
+                    KeyCode::Char('a'),
+                    KeyModifiers::CTRL
+                )),
+                Event::Key(KeyEvent::with_modifiers(
+                    KeyCode::Char('c'),
+                    KeyModifiers::CTRL
+                )),
+                Event::Key(KeyEvent::with_modifiers(
+                    KeyCode::Char('z'),
+                    KeyModifiers::CTRL
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_alt_key() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1ba");
+        assert_eq!(
+            events,
+            vec![Event::Key(KeyEvent::with_modifiers(
+                KeyCode::Char('a'),
+                KeyModifiers::ALT
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_parse_modified_arrows() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[1;2A\x1b[1;5D\x1b[1;6C");
+        assert_eq!(
+            events,
+            vec![
+                Event::Key(KeyEvent::with_modifiers(KeyCode::Up, KeyModifiers::SHIFT)),
+                Event::Key(KeyEvent::with_modifiers(KeyCode::Left, KeyModifiers::CTRL)),
+                Event::Key(KeyEvent::with_modifiers(
+                    KeyCode::Right,
+                    KeyModifiers::CTRL | KeyModifiers::SHIFT
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_modified_tilde_key() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[5;3~");
+        assert_eq!(
+            events,
+            vec![Event::Key(KeyEvent::with_modifiers(
+                KeyCode::PageUp,
+                KeyModifiers::ALT
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_parse_lone_esc_after_timeout() {
+        let mut parser = Parser::new();
+        assert!(parser.parse(b"\x1b").is_empty());
+
+        let events = parser.finish_incomplete();
+        assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Esc))]);
+    }
+
     #[test]
     fn test_parse_multiple() {
         let mut parser = Parser::new();
@@ -445,6 +1095,169 @@ mod tests {
             panic!("Expected Mouse event");
         }
     }
+
+    #[test]
+    fn test_parse_mouse_drag_and_move() {
+        let mut parser = Parser::new();
+        // X10: button 0 with the motion bit (32 + 32), then no button (32 + 35).
+        let events = parser.parse(b"\x1b[M@!!\x1b[MC+&");
+        assert_eq!(
+            events,
+            vec![
+                Event::Mouse(MouseEvent::new(0, 0, MouseKind::Drag)),
+                Event::Mouse(MouseEvent::new(10, 5, MouseKind::Moved)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sgr_mouse() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[<0;3;4M\x1b[<32;5;4M\x1b[<35;120;40M\x1b[<0;5;4m");
+        assert_eq!(
+            events,
+            vec![
+                Event::Mouse(MouseEvent::new(2, 3, MouseKind::LeftClick)),
+                Event::Mouse(MouseEvent::new(4, 3, MouseKind::Drag)),
+                Event::Mouse(MouseEvent::new(119, 39, MouseKind::Moved)),
+                Event::Mouse(MouseEvent::new(4, 3, MouseKind::Other)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_focus_events() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[I\x1b[Oa");
+        assert_eq!(
+            events,
+            vec![
+                Event::FocusGained,
+                Event::FocusLost,
+                Event::Key(KeyEvent::new(KeyCode::Char('a'))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_split_focus_event() {
+        let mut parser = Parser::new();
+        assert!(parser.parse(b"\x1b").is_empty());
+        assert!(parser.parse(b"[").is_empty());
+        assert!(parser.has_pending_state());
+        assert_eq!(parser.parse(b"O"), vec![Event::FocusLost]);
+        assert!(!parser.has_pending_state());
+    }
+
+    #[test]
+    fn test_parse_cursor_position_report() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[12;34Rx");
+        assert_eq!(
+            events,
+            vec![
+                Event::CursorPosition(33, 11),
+                Event::Key(KeyEvent::new(KeyCode::Char('x'))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_cursor_position_report_by_default() {
+        let mut parser = Parser::new();
+        let events = parser.parse(b"\x1b[12;34R");
+        assert!(!events.iter().any(|e| matches!(e, Event::Key(_))));
+        assert_eq!(events, vec![Event::CursorPosition(33, 11)]);
+    }
+
+    #[test]
+    fn test_parse_modified_f1_to_f4() {
+        let ctrl = |n| KeyEvent::with_modifiers(KeyCode::F(n), KeyModifiers::CTRL);
+        let mut parser = Parser::new();
+        assert_eq!(
+            parser.parse(b"\x1b[1;5P\x1b[1;5R"),
+            vec![Event::Key(ctrl(1)), Event::Key(ctrl(3))]
+        );
+
+        // The F3 form is only a first-row report to a parser expecting one.
+        let mut parser = Parser::new().cursor_reports(true);
+        assert_eq!(
+            parser.parse(b"\x1b[1;5R\x1b[2;5R"),
+            vec![Event::CursorPosition(4, 0), Event::CursorPosition(4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_mouse_state_double_click() {
+        use crate::clock::{Clock, mocks::MockClock};
+
+        let clock = MockClock::new();
+        let mut state = MouseState::new(Duration::from_millis(300));
+        let click = |x, y| MouseEvent::new(x, y, MouseKind::LeftClick);
+
+        assert_eq!(state.track(click(3, 4), clock.now()), click(3, 4));
+        clock.sleep(Duration::from_millis(200));
+        assert_eq!(
+            state.track(click(3, 4), clock.now()).kind,
+            MouseKind::DoubleClick
+        );
+
+        // A third click starts a new pair.
+        assert_eq!(state.track(click(3, 4), clock.now()), click(3, 4));
+    }
+
+    #[test]
+    fn test_mouse_state_slow_or_moved_click_stays_single() {
+        use crate::clock::{Clock, mocks::MockClock};
+
+        let clock = MockClock::new();
+        let mut state = MouseState::new(Duration::from_millis(300));
+        let click = |x, y| MouseEvent::new(x, y, MouseKind::LeftClick);
+
+        state.track(click(3, 4), clock.now());
+        clock.sleep(Duration::from_millis(301));
+        assert_eq!(state.track(click(3, 4), clock.now()), click(3, 4));
+
+        clock.sleep(Duration::from_millis(10));
+        assert_eq!(state.track(click(4, 4), clock.now()), click(4, 4));
+
+        // Other events pass through untouched.
+        let scroll = MouseEvent::new(4, 4, MouseKind::ScrollUp);
+        assert_eq!(state.track(scroll.clone(), clock.now()), scroll);
+    }
+
+    #[test]
+    fn test_key_event_constructors() {
+        assert_eq!(KeyEvent::char('a'), KeyEvent::new(KeyCode::Char('a')));
+        assert_eq!(
+            KeyEvent::ctrl('c'),
+            KeyEvent::with_modifiers(KeyCode::Char('c'), KeyModifiers::CTRL)
+        );
+        assert_eq!(
+            KeyEvent::alt('x'),
+            KeyEvent::with_modifiers(KeyCode::Char('x'), KeyModifiers::ALT)
+        );
+        // Matches what the parser produces for Ctrl+C.
+        let mut parser = Parser::new();
+        assert_eq!(parser.parse(b"\x03"), vec![Event::Key(KeyEvent::ctrl('c'))]);
+    }
+
+    #[test]
+    fn test_key_display() {
+        assert_eq!(KeyEvent::ctrl('c').to_string(), "Ctrl+C");
+        assert_eq!(
+            KeyEvent::with_modifiers(KeyCode::Enter, KeyModifiers::ALT).to_string(),
+            "Alt+Enter"
+        );
+        assert_eq!(KeyEvent::new(KeyCode::F(5)).to_string(), "F5");
+        assert_eq!(KeyEvent::char('q').to_string(), "q");
+        assert_eq!(
+            KeyEvent::with_modifiers(KeyCode::Up, KeyModifiers::CTRL | KeyModifiers::SHIFT)
+                .to_string(),
+            "Ctrl+Shift+Up"
+        );
+        assert_eq!(KeyCode::Char(' ').to_string(), "Space");
+    }
 }
 
 #[cfg(test)]
@@ -469,6 +1282,16 @@ mod integration_tests {
         assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Char('a')))]);
     }
 
+    #[test]
+    fn test_input_cursor_reports() {
+        let mock = MockSystem::new();
+        mock.push_input(b"\x1b[1;5R");
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut input = Input::new().cursor_reports(true);
+
+        assert_eq!(input.read(&term), vec![Event::CursorPosition(4, 0)]);
+    }
+
     #[test]
     fn test_input_esc_timeout() {
         // Arrange: Lone Esc byte
@@ -485,6 +1308,18 @@ mod integration_tests {
         assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Esc))]);
     }
 
+    #[test]
+    fn test_input_read_timeout_without_input() {
+        let term = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let mut input = Input::new();
+
+        let start = std::time::Instant::now();
+        let events = input.read_timeout(&term, Duration::from_secs(5));
+
+        assert!(events.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
     #[test]
     fn test_input_split_arrow() {
         // Arrange: Split Up Arrow sequence (\x1b[A)
@@ -506,4 +1341,40 @@ mod integration_tests {
         // Assert
         assert_eq!(events, vec![Event::Key(KeyEvent::new(KeyCode::Up))]);
     }
+
+    #[test]
+    fn test_input_small_buffer_large_paste() {
+        // Arrange: a paste interleaving plain text, arrows and multi-byte chars,
+        // read through a buffer small enough to split every sequence.
+        let chunk = "ab\x1b[Aéc";
+        let paste = chunk.repeat(50);
+        let mock = MockSystem::new();
+        mock.push_input(paste.as_bytes());
+
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut input = Input::new().buffer_size(3);
+
+        // Act: keep reading until the mock input is exhausted
+        let mut events = Vec::new();
+        loop {
+            let batch = input.read(&term);
+            if batch.is_empty() {
+                break;
+            }
+            events.extend(batch);
+        }
+
+        // Assert
+        let expected = [
+            Event::Key(KeyEvent::new(KeyCode::Char('a'))),
+            Event::Key(KeyEvent::new(KeyCode::Char('b'))),
+            Event::Key(KeyEvent::new(KeyCode::Up)),
+            Event::Key(KeyEvent::new(KeyCode::Char('é'))),
+            Event::Key(KeyEvent::new(KeyCode::Char('c'))),
+        ];
+        assert_eq!(events.len(), expected.len() * 50);
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event, &expected[i % expected.len()]);
+        }
+    }
 }
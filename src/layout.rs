@@ -3,6 +3,10 @@
 //! The core type is [`Rect`], which represents a rectangular area on the screen.
 //! The [`Layout`] engine can split a [`Rect`] into multiple sub-rectangles based on [`Constraint`]s.
 
+use std::collections::HashMap;
+
+use crate::input::MouseEvent;
+
 /// The direction in which a rectangle is split.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -12,6 +16,24 @@ pub enum Direction {
     Vertical,
 }
 
+/// Horizontal placement of content within its area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Vertical placement of content within its area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
 /// Constraints used to define the size of a layout segment.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Constraint {
@@ -27,9 +49,29 @@ pub enum Constraint {
     /// A ratio of the available space (e.g., `Ratio(1, 3)` for one third).
     Ratio(u32, u32),
     /// Takes up `Fill` space, but is at least `u16` cells.
+    ///
+    /// The floor is kept even if fixed segments have to shrink to make room.
     Min(u16),
     /// Takes up `Fill` space, but is at most `u16` cells.
+    ///
+    /// Space beyond the ceiling goes to the other flexible segments.
     Max(u16),
+    /// Takes a share of the remaining space proportional to its weight.
+    ///
+    /// `Fill`, `Min` and `Max` count as weight 1, so `[Weight(2), Fill]`
+    /// splits the space 2:1.
+    Weight(u16),
+}
+
+impl Constraint {
+    /// The weight of a flexible segment, or `None` for fixed ones.
+    fn weight(&self) -> Option<u32> {
+        match self {
+            Constraint::Weight(w) => Some(*w as u32),
+            Constraint::Fill | Constraint::Min(_) | Constraint::Max(_) => Some(1),
+            Constraint::Length(_) | Constraint::Percentage(_) | Constraint::Ratio(..) => None,
+        }
+    }
 }
 
 /// A rectangular area on the screen.
@@ -57,8 +99,10 @@ impl Rect {
     }
 
     /// Returns the total number of cells in the rectangle.
-    pub fn area(&self) -> u16 {
-        self.width * self.height
+    ///
+    /// The product is computed in `u32`, since it can exceed `u16::MAX`.
+    pub fn area(&self) -> u32 {
+        u32::from(self.width) * u32::from(self.height)
     }
 
     /// Returns the x-coordinate of the left edge.
@@ -80,6 +124,51 @@ impl Rect {
     pub fn bottom(&self) -> u16 {
         self.y + self.height
     }
+
+    /// Returns whether the cell at `(x, y)` lies inside the rectangle.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.left() && x < self.right() && y >= self.top() && y < self.bottom()
+    }
+
+    /// Returns whether a mouse event happened inside the rectangle.
+    ///
+    /// Mouse coordinates are 0-based screen cells, the same space as the
+    /// buffer and the rectangles handed out by [`Layout::split`].
+    pub fn contains_mouse(&self, event: &MouseEvent) -> bool {
+        self.contains(event.x, event.y)
+    }
+
+    /// Returns the overlap of two rectangles.
+    ///
+    /// Disjoint rectangles give an empty rectangle (zero width or height).
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Returns the rectangle shrunk by `margin` cells on every side.
+    ///
+    /// A rectangle too small for the margin collapses to zero width/height.
+    pub fn inner(&self, margin: u16) -> Rect {
+        Rect::new(
+            self.x.saturating_add(margin),
+            self.y.saturating_add(margin),
+            self.width.saturating_sub(margin.saturating_mul(2)),
+            self.height.saturating_sub(margin.saturating_mul(2)),
+        )
+    }
 }
 
 /// A layout engine that divides a rectangle into sub-rectangles based on constraints.
@@ -88,6 +177,8 @@ pub struct Layout {
     pub direction: Direction,
     /// The constraints for each segment.
     pub constraints: Vec<Constraint>,
+    /// The number of empty cells between adjacent segments.
+    pub spacing: u16,
 }
 
 impl Layout {
@@ -96,61 +187,169 @@ impl Layout {
         Self {
             direction,
             constraints,
+            spacing: 0,
         }
     }
 
+    /// Creates a layout that splits top-to-bottom.
+    ///
+    /// Accepts an array as well as a `Vec`:
+    /// `Layout::vertical([Constraint::Length(1), Constraint::Fill])`.
+    pub fn vertical(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(Direction::Vertical, constraints.into())
+    }
+
+    /// Creates a layout that splits side-by-side.
+    pub fn horizontal(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(Direction::Horizontal, constraints.into())
+    }
+
+    /// Sets the number of empty cells left between adjacent segments.
+    ///
+    /// The gaps are reserved before the constraints are resolved, so
+    /// percentages and fills share only the space that remains.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
     /// Splits the given rectangle into sub-rectangles.
     ///
     /// The number of returned rectangles matches the number of constraints.
+    /// `Length`, `Percentage` and `Ratio` segments are sized first; the rest
+    /// is shared among the flexible ones in proportion to their weights,
+    /// honoring each `Min` floor and `Max` ceiling. If the segments don't fit,
+    /// fixed segments shrink (last first) before `Min` segments do. Space lost
+    /// to rounding goes to the heaviest `Fill`, `Min` or `Weight` segment (the
+    /// last one on ties, or else the last segment that isn't a `Max`), so the
+    /// rectangles cover `rect` exactly.
     pub fn split(&self, rect: Rect) -> Vec<Rect> {
         let mut rects = Vec::new();
-        let total_space = match &self.direction {
+        let gaps = self
+            .spacing
+            .saturating_mul(self.constraints.len().saturating_sub(1) as u16);
+        let extent = match &self.direction {
             Direction::Horizontal => rect.width,
             Direction::Vertical => rect.height,
         };
+        let total_space = extent.saturating_sub(gaps);
 
         let start_x = rect.x;
         let start_y = rect.y;
         let mut offset = 0;
 
-        // 1. Calculate used space and count fills
-        let mut used_space = 0;
-        let mut flex_count = 0;
-
-        for c in &self.constraints {
-            match c {
-                Constraint::Length(l) => used_space += l,
-                Constraint::Percentage(p) => used_space += (p * total_space) / 100,
-                Constraint::Ratio(n, d) => used_space += (total_space as u32 * n / d) as u16,
-                Constraint::Fill | Constraint::Min(_) | Constraint::Max(_) => flex_count += 1,
+        // 1. Size the fixed segments; flexible ones are resolved below.
+        let mut resolved: Vec<Option<u16>> = self
+            .constraints
+            .iter()
+            .map(|c| match c {
+                Constraint::Length(l) => Some(*l),
+                Constraint::Percentage(p) => Some((p * total_space) / 100),
+                Constraint::Ratio(n, d) => Some((total_space as u32 * n / d) as u16),
+                Constraint::Fill
+                | Constraint::Min(_)
+                | Constraint::Max(_)
+                | Constraint::Weight(_) => None,
+            })
+            .collect();
+        let fixed_space = resolved
+            .iter()
+            .flatten()
+            .fold(0u16, |sum, s| sum.saturating_add(*s));
+
+        // 2. Share the remaining space by weight. Whenever a `Max` would get
+        // more than its ceiling or a `Min` less than its floor, pin it to that
+        // bound and share again among the segments still open.
+        let mut remaining = total_space.saturating_sub(fixed_space);
+        loop {
+            let open_weight: u32 = self
+                .constraints
+                .iter()
+                .zip(&resolved)
+                .filter(|(_, size)| size.is_none())
+                .filter_map(|(c, _)| c.weight())
+                .sum();
+            // The share of a weight-1 segment; `Min` and `Max` are weight 1.
+            let Some(share) = (remaining as u32).checked_div(open_weight) else {
+                break;
+            };
+            let share = share as u16;
+
+            let bound = self.constraints.iter().zip(&resolved).enumerate().find_map(
+                |(i, (c, size))| match (c, size) {
+                    (Constraint::Max(n), None) if *n < share => Some((i, *n)),
+                    (Constraint::Min(n), None) if *n > share => Some((i, *n)),
+                    _ => None,
+                },
+            );
+
+            match bound {
+                Some((i, n)) => {
+                    resolved[i] = Some(n);
+                    remaining = remaining.saturating_sub(n);
+                }
+                None => {
+                    for (c, size) in self.constraints.iter().zip(resolved.iter_mut()) {
+                        if size.is_none() {
+                            let weight = c.weight().unwrap_or(0);
+                            *size = Some((remaining as u32 * weight / open_weight) as u16);
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        let mut sizes: Vec<u16> = resolved.into_iter().map(|s| s.unwrap_or(0)).collect();
+
+        // 3. If `Min` floors overflow the rect, take the excess from the fixed
+        // segments first, then from the `Min` segments, last to first.
+        let used: u16 = sizes.iter().fold(0, |sum, s| sum.saturating_add(*s));
+        let mut overflow = used.saturating_sub(total_space);
+        for shrink_min in [false, true] {
+            for (c, size) in self.constraints.iter().zip(sizes.iter_mut()).rev() {
+                if matches!(c, Constraint::Min(_)) == shrink_min {
+                    let cut = (*size).min(overflow);
+                    *size -= cut;
+                    overflow -= cut;
+                }
             }
         }
 
-        // 2. Calculate size of one `Fill` unit
-        let flex_size = if flex_count > 0 {
-            total_space.saturating_sub(used_space) / flex_count
-        } else {
-            0
-        };
-
-        // 3. Create rects
-        for c in &self.constraints {
-            let size = match c {
-                Constraint::Length(l) => *l,
-                Constraint::Percentage(p) => (p * total_space) / 100,
-                Constraint::Fill => flex_size,
-                Constraint::Ratio(n, d) => (total_space as u32 * n / d) as u16,
-                Constraint::Min(n) => flex_size.max(*n),
-                Constraint::Max(n) => flex_size.min(*n),
-            };
+        // 4. Hand space lost to rounding to the heaviest segment that can
+        // grow, so the segments tile the whole rect. `Max` segments are never
+        // grown.
+        let used: u16 = sizes.iter().fold(0, |sum, s| sum.saturating_add(*s));
+        let leftover = total_space.saturating_sub(used);
+        let target = self
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !matches!(c, Constraint::Max(_)))
+            .filter_map(|(i, c)| c.weight().map(|w| (w, i)))
+            .max()
+            .map(|(_, i)| i)
+            .or_else(|| {
+                self.constraints
+                    .iter()
+                    .rposition(|c| !matches!(c, Constraint::Max(_)))
+            });
+        if let Some(i) = target {
+            sizes[i] += leftover;
+        }
 
+        // 5. Create rects. When the gaps don't fit, spread the space that
+        // was actually reserved for them so no rect starts past the end.
+        let gap_count = sizes.len().saturating_sub(1).max(1) as u16;
+        let reserved = extent - total_space;
+        for (i, size) in sizes.into_iter().enumerate() {
             let sub_rect = match &self.direction {
                 Direction::Horizontal => Rect::new(start_x + offset, start_y, size, rect.height),
                 Direction::Vertical => Rect::new(start_x, start_y + offset, rect.width, size),
             };
 
             rects.push(sub_rect);
-            offset += size;
+            let gap = reserved / gap_count + u16::from((i as u16) < reserved % gap_count);
+            offset = offset.saturating_add(size).saturating_add(gap).min(extent);
         }
 
         rects
@@ -164,16 +363,200 @@ impl Layout {
         let rects = self.split(rect);
         rects.try_into().expect("Layout constraints count mismatch")
     }
+
+    /// Splits the given rectangle into `N` areas, ready to destructure:
+    /// `let [header, body] = Layout::vertical([..]).areas(rect);`.
+    ///
+    /// Same as [`split_to`](Self::split_to).
+    ///
+    /// # Panics
+    /// Panics if the number of constraints does not match the array size `N`.
+    pub fn areas<const N: usize>(&self, rect: Rect) -> [Rect; N] {
+        self.split_to(rect)
+    }
+
+    /// Splits the given rectangle into an `R`×`C` grid of equal cells, indexed
+    /// as `cells[row][col]`.
+    ///
+    /// When the size doesn't divide evenly, the remainder goes to the last row
+    /// and column.
+    ///
+    /// # Panics
+    /// Panics if `R` or `C` is zero.
+    pub fn grid_to<const R: usize, const C: usize>(rect: Rect) -> [[Rect; C]; R] {
+        assert!(R > 0 && C > 0, "Grid dimensions must be non-zero");
+
+        let cell_width = rect.width / C as u16;
+        let cell_height = rect.height / R as u16;
+
+        std::array::from_fn(|row| {
+            std::array::from_fn(|col| {
+                let x = cell_width * col as u16;
+                let y = cell_height * row as u16;
+                let width = if col == C - 1 {
+                    rect.width - x
+                } else {
+                    cell_width
+                };
+                let height = if row == R - 1 {
+                    rect.height - y
+                } else {
+                    cell_height
+                };
+                Rect::new(rect.x + x, rect.y + y, width, height)
+            })
+        })
+    }
+
+    /// Splits the given rectangle and returns the sub-rectangles keyed by name.
+    ///
+    /// `names[i]` labels the segment produced by `constraints[i]`, so callers can
+    /// write `areas["header"]` instead of relying on positions.
+    ///
+    /// # Panics
+    /// Panics if the number of names does not match the number of constraints,
+    /// or if a name is used twice.
+    pub fn split_named(&self, rect: Rect, names: &[&str]) -> HashMap<String, Rect> {
+        assert_eq!(
+            names.len(),
+            self.constraints.len(),
+            "Layout names count mismatch"
+        );
+
+        let mut areas = HashMap::with_capacity(names.len());
+        for (name, area) in names.iter().zip(self.split(rect)) {
+            if areas.insert(name.to_string(), area).is_some() {
+                panic!("Duplicate layout name: {name}");
+            }
+        }
+        areas
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_layout_weights() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![
+                Constraint::Weight(2),
+                Constraint::Weight(1),
+                Constraint::Weight(1),
+            ],
+        );
+        let widths: Vec<u16> = layout
+            .split(Rect::new(0, 0, 100, 1))
+            .iter()
+            .map(|r| r.width)
+            .collect();
+        assert_eq!(widths, [50, 25, 25]);
+
+        // The rounding leftover goes to the heaviest segment.
+        let widths: Vec<u16> = layout
+            .split(Rect::new(0, 0, 102, 1))
+            .iter()
+            .map(|r| r.width)
+            .collect();
+        assert_eq!(widths, [52, 25, 25]);
+        assert_eq!(widths.iter().sum::<u16>(), 102);
+    }
+
+    #[test]
+    fn test_layout_weight_with_fill_and_length() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![
+                Constraint::Length(20),
+                Constraint::Fill,
+                Constraint::Weight(3),
+            ],
+        );
+        let rects = layout.split(Rect::new(0, 0, 100, 1));
+        assert_eq!(rects[1], Rect::new(20, 0, 20, 1));
+        assert_eq!(rects[2], Rect::new(40, 0, 60, 1));
+    }
+
+    #[test]
+    fn test_layout_shortcut_constructors() {
+        let rect = Rect::new(0, 0, 30, 10);
+        let constraints = [Constraint::Length(3), Constraint::Fill];
+
+        let vertical = Layout::vertical(constraints);
+        assert_eq!(vertical.direction, Direction::Vertical);
+        assert_eq!(
+            vertical.split(rect),
+            Layout::new(Direction::Vertical, constraints.to_vec()).split(rect)
+        );
+
+        let [left, right] = Layout::horizontal(constraints).spacing(1).areas(rect);
+        assert_eq!(left, Rect::new(0, 0, 3, 10));
+        assert_eq!(right, Rect::new(4, 0, 26, 10));
+    }
+
+    #[test]
+    fn test_rect_intersection() {
+        let a = Rect::new(0, 0, 10, 10);
+        let b = Rect::new(5, 8, 10, 10);
+        assert_eq!(a.intersection(b), Rect::new(5, 8, 5, 2));
+
+        let disjoint = a.intersection(Rect::new(20, 0, 5, 5));
+        assert_eq!(disjoint.area(), 0);
+    }
+
+    #[test]
+    fn test_rect_contains_parsed_click() {
+        use crate::input::{Event, Parser};
+
+        // Left clicks at screen cells (0, 0) and (10, 5), X10 encoded.
+        let events = Parser::new().parse(b"\x1b[M !!\x1b[M +&");
+        let clicks: Vec<_> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                Event::Mouse(mouse) => Some(mouse),
+                _ => None,
+            })
+            .collect();
+        assert_eq!((clicks[0].x, clicks[0].y), (0, 0));
+
+        let button = Rect::new(8, 5, 4, 1);
+        assert!(!button.contains_mouse(&clicks[0]));
+        assert!(button.contains_mouse(&clicks[1]));
+        assert!(!Rect::new(8, 4, 4, 1).contains_mouse(&clicks[1]));
+    }
+
+    #[test]
+    fn test_rect_union() {
+        let a = Rect::new(2, 2, 3, 3);
+        let b = Rect::new(6, 1, 2, 2);
+        assert_eq!(a.union(b), Rect::new(2, 1, 6, 4));
+    }
+
+    #[test]
+    fn test_rect_contains_edges() {
+        let rect = Rect::new(2, 3, 4, 2);
+        assert!(rect.contains(2, 3));
+        assert!(rect.contains(5, 4));
+        assert!(!rect.contains(6, 4));
+        assert!(!rect.contains(5, 5));
+        assert!(!rect.contains(1, 3));
+    }
+
+    #[test]
+    fn test_rect_inner_saturates() {
+        assert_eq!(Rect::new(0, 0, 10, 6).inner(2), Rect::new(2, 2, 6, 2));
+        let tiny = Rect::new(1, 1, 3, 1).inner(2);
+        assert_eq!((tiny.width, tiny.height), (0, 0));
+    }
+
     #[test]
     fn test_rect_calculations() {
         let rect = Rect::new(10, 10, 20, 5);
         assert_eq!(rect.area(), 100);
+        assert_eq!(Rect::new(0, 0, 300, 300).area(), 90_000);
+        assert_eq!(Rect::new(0, 0, u16::MAX, u16::MAX).area(), 4_294_836_225);
         assert_eq!(rect.left(), 10);
         assert_eq!(rect.right(), 30);
         assert_eq!(rect.top(), 10);
@@ -191,7 +574,8 @@ mod tests {
 
         assert_eq!(rects.len(), 2);
         assert_eq!(rects[0], Rect::new(0, 0, 10, 2));
-        assert_eq!(rects[1], Rect::new(0, 2, 10, 5));
+        // The last segment takes the 3 rows the constraints leave unused.
+        assert_eq!(rects[1], Rect::new(0, 2, 10, 8));
     }
 
     #[test]
@@ -210,6 +594,83 @@ mod tests {
         assert_eq!(rects[2].y, 6);
     }
 
+    #[test]
+    fn test_layout_split_fill_remainder() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Fill, Constraint::Fill, Constraint::Fill],
+        );
+        let rect = Rect::new(5, 0, 100, 1);
+        let rects = layout.split(rect);
+
+        assert_eq!(rects.iter().map(|r| r.width).sum::<u16>(), 100);
+        assert_eq!(rects[0].width, 33);
+        assert_eq!(rects[2].width, 34);
+        assert_eq!(rects[2].right(), rect.right());
+    }
+
+    #[test]
+    fn test_layout_split_percentage_remainder() {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ],
+        );
+        let rect = Rect::new(0, 0, 10, 100);
+        let rects = layout.split(rect);
+
+        assert_eq!(rects[2].height, 34);
+        assert_eq!(rects[2].bottom(), rect.bottom());
+    }
+
+    #[test]
+    fn test_layout_split_spacing() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+        )
+        .spacing(2);
+        let rects = layout.split(Rect::new(0, 0, 100, 10));
+
+        assert_eq!(rects[0], Rect::new(0, 0, 49, 10));
+        assert_eq!(rects[1], Rect::new(51, 0, 49, 10));
+    }
+
+    #[test]
+    fn test_layout_split_spacing_larger_than_rect() {
+        let constraints = vec![Constraint::Fill, Constraint::Fill, Constraint::Fill];
+        let rect = Rect::new(0, 0, 3, 1);
+
+        let rects = Layout::horizontal(constraints.clone())
+            .spacing(2)
+            .split(rect);
+        assert!(rects.iter().all(|r| r.x <= rect.right()));
+        assert_eq!(rects[1].x, 2);
+        assert_eq!(rects[2].x, 3);
+
+        let rects = Layout::horizontal(constraints)
+            .spacing(u16::MAX)
+            .split(rect);
+        assert!(rects.iter().all(|r| r.x <= rect.right() && r.width == 0));
+    }
+
+    #[test]
+    fn test_layout_split_spacing_with_fill() {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Length(2), Constraint::Fill, Constraint::Fill],
+        )
+        .spacing(1);
+        let rects = layout.split(Rect::new(0, 0, 10, 10));
+
+        // 10 rows - 2 gaps - 2 fixed leaves 6 for the fills.
+        assert_eq!(rects[1], Rect::new(0, 3, 10, 3));
+        assert_eq!(rects[2], Rect::new(0, 7, 10, 3));
+    }
+
     #[test]
     fn test_layout_split_to() {
         let layout = Layout::new(
@@ -224,6 +685,24 @@ mod tests {
         assert_eq!(right.x, 50);
     }
 
+    #[test]
+    fn test_layout_grid_to() {
+        let cells: [[Rect; 3]; 2] = Layout::grid_to(Rect::new(0, 0, 60, 40));
+
+        assert_eq!(cells[0][0], Rect::new(0, 0, 20, 20));
+        assert_eq!(cells[0][2], Rect::new(40, 0, 20, 20));
+        assert_eq!(cells[1][1], Rect::new(20, 20, 20, 20));
+    }
+
+    #[test]
+    fn test_layout_grid_to_remainder() {
+        let cells: [[Rect; 3]; 2] = Layout::grid_to(Rect::new(5, 5, 10, 5));
+
+        assert_eq!(cells[0][0], Rect::new(5, 5, 3, 2));
+        assert_eq!(cells[0][2], Rect::new(11, 5, 4, 2));
+        assert_eq!(cells[1][2], Rect::new(11, 7, 4, 3));
+    }
+
     #[test]
     #[should_panic]
     fn test_layout_split_to_mismatch() {
@@ -232,6 +711,31 @@ mod tests {
         let _: [Rect; 2] = layout.split_to(rect); // Should panic
     }
 
+    #[test]
+    fn test_layout_split_named() {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![
+                Constraint::Length(1),
+                Constraint::Fill,
+                Constraint::Length(1),
+            ],
+        );
+        let areas = layout.split_named(Rect::new(0, 0, 10, 10), &["header", "body", "footer"]);
+
+        assert_eq!(areas.len(), 3);
+        assert_eq!(areas["header"], Rect::new(0, 0, 10, 1));
+        assert_eq!(areas["body"], Rect::new(0, 1, 10, 8));
+        assert_eq!(areas["footer"], Rect::new(0, 9, 10, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Layout names count mismatch")]
+    fn test_layout_split_named_mismatch() {
+        let layout = Layout::new(Direction::Vertical, vec![Constraint::Fill]);
+        layout.split_named(Rect::new(0, 0, 10, 10), &["a", "b"]);
+    }
+
     #[test]
     fn test_layout_split_ratio() {
         let layout = Layout::new(
@@ -265,4 +769,42 @@ mod tests {
         let rects_max = layout_max.split(rect);
         assert_eq!(rects_max[1].height, 40);
     }
+
+    #[test]
+    fn test_layout_split_min_overflows_fixed() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Length(90), Constraint::Min(20)],
+        );
+        let rects = layout.split(Rect::new(0, 0, 100, 1));
+
+        assert_eq!(rects[1].width, 20);
+        assert_eq!(rects[0].width, 80);
+        assert_eq!(rects[1].right(), 100);
+    }
+
+    #[test]
+    fn test_layout_split_max_releases_space() {
+        let layout = Layout::new(
+            Direction::Horizontal,
+            vec![Constraint::Max(10), Constraint::Fill],
+        );
+        let rects = layout.split(Rect::new(0, 0, 100, 1));
+
+        assert_eq!(rects[0], Rect::new(0, 0, 10, 1));
+        assert_eq!(rects[1], Rect::new(10, 0, 90, 1));
+    }
+
+    #[test]
+    fn test_layout_split_min_with_fill() {
+        let layout = Layout::new(
+            Direction::Vertical,
+            vec![Constraint::Fill, Constraint::Min(60)],
+        );
+        let rects = layout.split(Rect::new(0, 0, 10, 100));
+
+        // The Fill gets what's left after the Min floor, not half.
+        assert_eq!(rects[0].height, 40);
+        assert_eq!(rects[1], Rect::new(0, 40, 10, 60));
+    }
 }
@@ -28,19 +28,25 @@
 //! ```
 
 use std::io;
-use std::thread;
-use std::time::Duration;
+use std::panic::PanicHookInfo;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 pub use crate::buffer::{Buffer, Cell};
+use crate::clock::{Clock, SystemClock};
 pub use crate::frame::Frame;
-pub use crate::input::{Event, Input, KeyCode, KeyEvent, MouseEvent, MouseKind};
-pub use crate::layout::{Constraint, Direction, Layout, Rect};
+pub use crate::input::{Event, Input, KeyCode, KeyEvent, MouseEvent, MouseKind, MouseState};
+pub use crate::layout::{Alignment, Constraint, Direction, Layout, Rect, VerticalAlignment};
 use crate::renderer::Renderer;
 pub use crate::style::{Color, Modifier, Style};
+pub use crate::subscription::Subscription;
+use crate::subscription::Subscriptions;
 use crate::terminal::Terminal;
+pub use crate::terminal::TestBackend;
 pub use crate::widgets::Widget;
 
 pub mod buffer;
+pub mod clock;
 pub mod frame;
 pub mod input;
 #[macro_use]
@@ -48,7 +54,10 @@ pub mod logger;
 pub mod layout;
 pub mod renderer;
 pub mod style;
+pub mod subscription;
 pub mod terminal;
+pub mod testing;
+pub mod util;
 pub mod widgets;
 
 /// Commands returned by the application to control the runtime flow.
@@ -58,6 +67,38 @@ pub enum Command {
     None,
     /// Stop the application and exit immediately.
     Quit,
+    /// Set the terminal's default background color (OSC 11).
+    ///
+    /// The original background is restored when the application exits.
+    /// [`Color::Reset`] restores it immediately.
+    SetBackground(Color),
+    /// Feed an [`Event`] to [`Application::on_event`] on the next loop
+    /// iteration, as if it came from the terminal.
+    ///
+    /// Useful for scripted demos that simulate keystrokes.
+    Inject(Event),
+    /// Set the terminal window title (OSC 0).
+    SetTitle(String),
+    /// Deliver a single [`Event::Tick`] to [`Application::on_event`] once the
+    /// duration has elapsed.
+    ///
+    /// Return another `Tick` when handling it to keep a timer going.
+    Tick(Duration),
+    /// Clear the screen and repaint every cell on the next frame.
+    ///
+    /// Use it when something else wrote to the terminal (e.g. a subprocess),
+    /// since the renderer otherwise only redraws cells it knows changed.
+    Redraw,
+    /// Like [`Command::None`], but tells the runtime the update didn't change
+    /// anything on screen, so the next frame needn't be drawn.
+    ///
+    /// Only has an effect with [`RunOptions::lazy_redraw`].
+    NoRedraw,
+    /// Apply several commands in order.
+    ///
+    /// A [`Command::Quit`] inside the batch stops the application once reached;
+    /// the commands after it are not applied.
+    Batch(Vec<Command>),
 }
 
 /// The core trait for a Phosphor application.
@@ -85,6 +126,15 @@ pub trait Application {
         None
     }
 
+    /// Declares the non-input sources of actions the runtime should watch.
+    ///
+    /// This is called on every iteration of the event loop, so the returned
+    /// [`Subscription`]s may depend on the current state (e.g. only run a timer
+    /// while an animation is playing).
+    fn subscriptions(&self) -> Vec<Subscription<'_, Self::Action>> {
+        Vec::new()
+    }
+
     /// Updates the application state based on an action.
     ///
     /// This is the only place where you should modify your application state.
@@ -98,6 +148,149 @@ pub trait Application {
     fn draw(&self, frame: &mut Frame);
 }
 
+/// Options controlling how [`run_with_options`] drives an application.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    ascii_only: bool,
+    tick_rate: Option<Duration>,
+    backspace_sends_del: bool,
+    profile: bool,
+    restore_on_panic: bool,
+    frame_rate: u32,
+    lazy_redraw: bool,
+    double_click: Option<Duration>,
+    truecolor: bool,
+    coalesce_runs: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            ascii_only: false,
+            tick_rate: None,
+            backspace_sends_del: true,
+            profile: false,
+            restore_on_panic: false,
+            frame_rate: 60,
+            lazy_redraw: false,
+            double_click: None,
+            truecolor: true,
+            coalesce_runs: false,
+        }
+    }
+}
+
+impl RunOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts widgets to ASCII glyphs (`+`, `-`, `|`) instead of Unicode
+    /// box-drawing characters.
+    ///
+    /// Use this on terminals with a non-UTF-8 locale, where box-drawing glyphs
+    /// render as garbage. Widgets read the setting from [`Frame::is_ascii_only`].
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Delivers an [`Event::Tick`] to [`Application::on_event`] whenever
+    /// `tick_rate` elapses without input.
+    ///
+    /// This is a lightweight alternative to an interval [`Subscription`] for
+    /// apps that only need a periodic wake-up. Disabled by default.
+    pub fn tick_rate(mut self, tick_rate: Duration) -> Self {
+        self.tick_rate = Some(tick_rate);
+        self
+    }
+
+    /// Sets whether `0x7f` is decoded as [`KeyCode::Backspace`] (default) or
+    /// [`KeyCode::Delete`].
+    ///
+    /// Terminals disagree on this; set it to `false` on setups where Backspace
+    /// sends `0x08` and the Delete key sends `0x7f`.
+    pub fn backspace_sends_del(mut self, backspace_sends_del: bool) -> Self {
+        self.backspace_sends_del = backspace_sends_del;
+        self
+    }
+
+    /// Logs per-frame timings (draw, diff, write) and the bytes emitted.
+    ///
+    /// Lines go through [`log!`], so call [`logger::init`] to see them.
+    pub fn profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Installs a panic hook for the duration of the run that restores the
+    /// terminal (raw mode off, main screen, cursor shown) before the panic
+    /// message is printed, so it lands on a readable screen.
+    ///
+    /// The previous hook still runs after the restore, and is put back when
+    /// the run ends normally. Disabled by default.
+    pub fn restore_on_panic(mut self, restore_on_panic: bool) -> Self {
+        self.restore_on_panic = restore_on_panic;
+        self
+    }
+
+    /// Caps the event loop at `frame_rate` iterations per second (default: 60).
+    ///
+    /// After handling input, the loop sleeps for whatever is left of the
+    /// frame's time budget, so a busy frame doesn't add a full frame of delay.
+    /// A rate of 0 is treated as 1.
+    pub fn frame_rate(mut self, frame_rate: u32) -> Self {
+        self.frame_rate = frame_rate.max(1);
+        self
+    }
+
+    /// Only redraws after the app handled an action, instead of every frame
+    /// (default: false).
+    ///
+    /// Suits event-driven apps whose [`Application::draw`] depends on nothing
+    /// but their state. The first frame and frames after a resize are always
+    /// drawn; an update returning [`Command::NoRedraw`] doesn't cause one.
+    pub fn lazy_redraw(mut self, lazy_redraw: bool) -> Self {
+        self.lazy_redraw = lazy_redraw;
+        self
+    }
+
+    /// Reports a second left click at the same cell within `threshold` of
+    /// the first as [`MouseKind::DoubleClick`] (see [`MouseState`]).
+    ///
+    /// Disabled by default, so every click arrives as
+    /// [`MouseKind::LeftClick`].
+    pub fn double_click(mut self, threshold: Duration) -> Self {
+        self.double_click = Some(threshold);
+        self
+    }
+
+    /// Sets whether [`Color::Rgb`] is written as 24-bit color (default: true).
+    ///
+    /// Turn it off on terminals without truecolor support to map RGB colors
+    /// to the nearest indexed color on output (see
+    /// [`Renderer::with_truecolor`](crate::renderer::Renderer::with_truecolor)).
+    pub fn truecolor(mut self, truecolor: bool) -> Self {
+        self.truecolor = truecolor;
+        self
+    }
+
+    /// Writes each run of changed cells sharing a style with a single cursor
+    /// move and SGR sequence (default: false).
+    ///
+    /// See [`render_runs_to_string`](crate::renderer::render_runs_to_string).
+    pub fn coalesce_runs(mut self, coalesce_runs: bool) -> Self {
+        self.coalesce_runs = coalesce_runs;
+        self
+    }
+
+    /// The time budget of one event loop iteration.
+    fn frame_budget(&self) -> Duration {
+        Duration::from_secs(1) / self.frame_rate
+    }
+}
+
 /// Entry point to run a Phosphor application.
 ///
 /// This function:
@@ -113,12 +306,21 @@ pub trait Application {
 /// # Panics
 /// If the application panics, this function catches the unwind, restores the
 /// terminal state (exits raw mode, shows cursor), and then resumes the panic.
-/// This ensures the terminal is not left in a broken state.
+/// This ensures the terminal is not left in a broken state. The panic message
+/// itself is printed before the unwind; enable
+/// [`RunOptions::restore_on_panic`] to restore the terminal before that too.
 pub fn run<App: Application>(app: App) -> io::Result<()> {
+    run_with_options(app, RunOptions::default())
+}
+
+/// Runs a Phosphor application with custom [`RunOptions`].
+///
+/// See [`run`] for details on the lifecycle and error handling.
+pub fn run_with_options<App: Application>(app: App, options: RunOptions) -> io::Result<()> {
     let terminal = Terminal::new()?;
-    let input = Input::new();
+    let input = Input::new().backspace_sends_del(options.backspace_sends_del);
     let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        run_app(app, terminal, input)
+        run_app(app, terminal, input, &SystemClock, &options)
     }));
 
     match res {
@@ -130,54 +332,339 @@ pub fn run<App: Application>(app: App) -> io::Result<()> {
     }
 }
 
+/// Runs an application against a [`TestBackend`] for `frames` iterations of
+/// the event loop and returns the [`Buffer`] drawn in each.
+///
+/// Use this for integration tests, or to render an animation frame by frame.
+/// The backend never blocks on input, so the loop advances even without
+/// events; queue input with [`TestBackend::push_input`] beforehand to script
+/// interaction. Fewer frames are returned if the app quits early.
+///
+/// # Errors
+/// Returns an [`io::Error`] if a write to the backend fails.
+pub fn run_headless<App: Application>(
+    app: App,
+    backend: TestBackend,
+    frames: usize,
+) -> io::Result<Vec<Buffer>> {
+    let terminal = Terminal::new_with_system(Box::new(backend))?;
+    let mut captured = Vec::with_capacity(frames);
+    if frames == 0 {
+        return Ok(captured);
+    }
+
+    run_loop(
+        app,
+        terminal,
+        Input::new(),
+        &SystemClock,
+        &RunOptions::default(),
+        &mut |buffer| {
+            captured.push(buffer.clone());
+            captured.len() < frames
+        },
+    )?;
+    Ok(captured)
+}
+
+/// Draws `app` once into a blank `width` x `height` [`Buffer`] and returns
+/// the ANSI sequence that paints it, without touching a terminal.
+///
+/// Use this to write a preview to a file or a pipe. The output positions each
+/// run of text with cursor moves, as [`render_runs_to_string`] does against a
+/// blank screen of the same size, and ends by resetting the style.
+///
+/// [`render_runs_to_string`]: crate::renderer::render_runs_to_string
+pub fn render_to_string<App: Application>(app: &App, width: u16, height: u16) -> String {
+    let blank = Buffer::new(width, height);
+    let mut buffer = blank.clone();
+    app.draw(&mut Frame::new(&mut buffer, Rect::new(0, 0, width, height)));
+
+    let mut out = renderer::render_runs_to_string(&blank, &buffer);
+    if !out.is_empty() {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
+/// The longest the event loop waits for input before checking the terminal
+/// size again.
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
 /// The internal event loop.
-fn run_app<App: Application>(mut app: App, terminal: Terminal, mut input: Input) -> io::Result<()> {
+fn run_app<App: Application>(
+    app: App,
+    terminal: Terminal,
+    input: Input,
+    clock: &dyn Clock,
+    options: &RunOptions,
+) -> io::Result<()> {
+    let _hook = options
+        .restore_on_panic
+        .then(|| PanicHook::install(terminal.restorer()));
+    run_loop(app, terminal, input, clock, options, &mut |_| true)
+}
+
+type Hook = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync>;
+
+/// A panic hook that runs `restore` before the previously installed hook,
+/// for [`RunOptions::restore_on_panic`]. Dropping it reinstalls the previous
+/// hook.
+struct PanicHook {
+    previous: Option<Arc<Hook>>,
+}
+
+impl PanicHook {
+    fn install(restore: impl Fn() + Send + Sync + 'static) -> Self {
+        let previous: Arc<Hook> = Arc::new(std::panic::take_hook());
+        let chained = Arc::clone(&previous);
+        std::panic::set_hook(Box::new(move |info| {
+            restore();
+            chained(info);
+        }));
+        Self {
+            previous: Some(previous),
+        }
+    }
+}
+
+impl Drop for PanicHook {
+    fn drop(&mut self) {
+        // Hooks can't be swapped while unwinding, so ours stays in that case.
+        let Some(previous) = self.previous.take() else {
+            return;
+        };
+        if std::thread::panicking() {
+            return;
+        }
+
+        drop(std::panic::take_hook());
+        match Arc::try_unwrap(previous) {
+            Ok(hook) => std::panic::set_hook(hook),
+            Err(shared) => std::panic::set_hook(Box::new(move |info| shared(info))),
+        }
+    }
+}
+
+/// The event loop, calling `on_frame` with each rendered buffer.
+///
+/// Stops when the app quits or `on_frame` returns `false`.
+fn run_loop<App: Application>(
+    mut app: App,
+    terminal: Terminal,
+    mut input: Input,
+    clock: &dyn Clock,
+    options: &RunOptions,
+    on_frame: &mut dyn FnMut(&Buffer) -> bool,
+) -> io::Result<()> {
+    let mut runtime = Runtime::new(&terminal, clock);
+
     // Check if the app wants to exit immediately
-    if let Command::Quit = app.init() {
+    if runtime.execute(app.init())? {
         return Ok(());
     }
 
     let (width, height) = terminal.size()?;
-    let mut renderer = Renderer::new(width, height);
+    let mut last_size = (width, height);
+    // With resize notifications, the size is only queried after a resize.
+    let watching_resize = terminal.watch_resize();
+    let mut renderer = Renderer::new(width, height)
+        .with_truecolor(options.truecolor)
+        .coalesce_runs(options.coalesce_runs);
+    let mut subscriptions = Subscriptions::default();
+    let mut next_tick = options.tick_rate.map(|rate| clock.now() + rate);
+    let mut mouse = options.double_click.map(MouseState::new);
 
     // Initial screen clear
-    terminal.write(b"\x1b[2J")?;
+    terminal.write_all(b"\x1b[2J")?;
 
     loop {
-        let (w, h) = terminal.size()?;
-        let mut next_buffer = Buffer::new(w, h);
-        let screen = Rect::new(0, 0, w, h);
-        let mut frame = Frame::new(&mut next_buffer, screen);
+        let frame_start = clock.now();
+        let (w, h) = if !watching_resize || terminal.take_resized() {
+            terminal.size()?
+        } else {
+            last_size
+        };
+
+        // Let the app react to a resize before it draws at the new size.
+        if (w, h) != last_size {
+            last_size = (w, h);
+            runtime.dirty = true;
+            if let Some(msg) = app.on_event(Event::Resize(w, h))
+                && runtime.execute(app.update(msg))?
+            {
+                return Ok(());
+            }
+        }
 
         // --- 1. Render Phase ---
-        app.draw(&mut frame);
-        renderer.render(&terminal, &next_buffer)?;
+        if runtime.dirty || !options.lazy_redraw {
+            runtime.dirty = false;
+            let mut next_buffer = Buffer::new(w, h);
+            let screen = Rect::new(0, 0, w, h);
+            let mut frame = Frame::new(&mut next_buffer, screen).ascii_only(options.ascii_only);
+
+            let draw_start = clock.now();
+            app.draw(&mut frame);
+            let draw = clock.now() - draw_start;
+            renderer.set_cursor(frame.cursor());
+            if std::mem::take(&mut runtime.repaint) {
+                renderer.invalidate();
+            }
+            let stats = renderer.render_with_stats(&terminal, &next_buffer, clock)?;
+            if options.profile {
+                log!(
+                    "frame: draw={:?} diff={:?} write={:?} bytes={}",
+                    draw,
+                    stats.diff,
+                    stats.write,
+                    stats.bytes
+                );
+            }
+            if !on_frame(&next_buffer) {
+                return Ok(());
+            }
+        }
+
+        // --- 2. Subscription Phase ---
+        let actions = subscriptions.poll(app.subscriptions(), clock.now(), &terminal);
+        for msg in actions {
+            if runtime.execute(app.update(msg))? {
+                return Ok(());
+            }
+        }
 
-        // --- 2. Input Phase ---
-        let events = input.read(&terminal);
-        for event in events {
+        // --- 3. Input Phase ---
+        // Injected events take the place of terminal input for this iteration.
+        // Otherwise wait for input until the next subscription or tick is due,
+        // but never longer than `INPUT_POLL`, so resizes are still noticed.
+        let now = clock.now();
+        let until_tick = next_tick.map(|deadline| deadline.saturating_duration_since(now));
+        let timeout = [
+            subscriptions.timeout(now),
+            until_tick,
+            runtime.timeout(now),
+            Some(INPUT_POLL),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(INPUT_POLL);
+        let mut events = if !runtime.injected.is_empty() {
+            std::mem::take(&mut runtime.injected)
+        } else {
+            input.read_timeout(&terminal, timeout)
+        };
+        if let (Some(deadline), Some(rate)) = (next_tick, options.tick_rate) {
+            let now = clock.now();
+            if !events.is_empty() {
+                next_tick = Some(now + rate);
+            } else if now >= deadline {
+                events.push(Event::Tick);
+                next_tick = Some(now + rate);
+            }
+        }
+        events.extend(runtime.due_ticks(clock.now()));
+        for mut event in events {
+            if let (Some(mouse), Event::Mouse(mouse_event)) = (&mut mouse, &event) {
+                event = Event::Mouse(mouse.track(mouse_event.clone(), clock.now()));
+            }
             // Map raw event -> App Action
             if let Some(msg) = app.on_event(event) {
                 // Update State
-                match app.update(msg) {
-                    Command::Quit => return Ok(()),
-                    Command::None => {}
+                if runtime.execute(app.update(msg))? {
+                    return Ok(());
+                }
+            }
+        }
+
+        // --- 4. Idle Phase ---
+        // Sleep for the rest of the frame budget to cap the frame rate.
+        let elapsed = clock.now() - frame_start;
+        clock.sleep(options.frame_budget().saturating_sub(elapsed));
+    }
+}
+
+/// Runtime state that [`Command`]s act on.
+struct Runtime<'a> {
+    terminal: &'a Terminal,
+    clock: &'a dyn Clock,
+    /// Events queued by [`Command::Inject`], delivered on the next iteration.
+    injected: Vec<Event>,
+    /// Deadlines of pending [`Command::Tick`]s.
+    ticks: Vec<Instant>,
+    /// Whether the app must be drawn again, for [`RunOptions::lazy_redraw`].
+    dirty: bool,
+    /// Whether [`Command::Redraw`] asked for a full repaint.
+    repaint: bool,
+}
+
+impl<'a> Runtime<'a> {
+    fn new(terminal: &'a Terminal, clock: &'a dyn Clock) -> Self {
+        Self {
+            terminal,
+            clock,
+            injected: Vec::new(),
+            ticks: Vec::new(),
+            dirty: true,
+            repaint: false,
+        }
+    }
+
+    /// Applies a [`Command`] to the runtime.
+    ///
+    /// Returns `true` if the application asked to quit.
+    fn execute(&mut self, command: Command) -> io::Result<bool> {
+        self.dirty |= command != Command::NoRedraw;
+        match command {
+            Command::None | Command::NoRedraw => {}
+            Command::Quit => return Ok(true),
+            Command::SetBackground(color) => self.terminal.set_background(color)?,
+            Command::Inject(event) => self.injected.push(event),
+            Command::SetTitle(title) => self.terminal.set_title(&title)?,
+            Command::Tick(after) => self.ticks.push(self.clock.now() + after),
+            Command::Redraw => self.repaint = true,
+            Command::Batch(commands) => {
+                for command in commands {
+                    if self.execute(command)? {
+                        return Ok(true);
+                    }
                 }
             }
         }
+        Ok(false)
+    }
 
-        // --- 3. Idle Phase ---
-        // Simple frame limiter (approx 60 FPS) to reduce CPU usage.
-        thread::sleep(Duration::from_millis(16));
+    /// Returns how long until the next pending tick, if any.
+    fn timeout(&self, now: Instant) -> Option<Duration> {
+        self.ticks
+            .iter()
+            .map(|deadline| deadline.saturating_duration_since(now))
+            .min()
+    }
+
+    /// Removes the ticks due at `now`, returning one [`Event::Tick`] for each.
+    fn due_ticks(&mut self, now: Instant) -> Vec<Event> {
+        let pending = self.ticks.len();
+        self.ticks.retain(|deadline| *deadline > now);
+        vec![Event::Tick; pending - self.ticks.len()]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::mocks::MockClock;
     use crate::input::{Event, KeyCode, KeyEvent};
     // Note: We use the mock system to simulate input without a real terminal
     use crate::terminal::mocks::MockSystem;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// The default frame budget, at 60 frames per second.
+    const FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
 
     struct TestApp;
 
@@ -218,9 +705,962 @@ mod tests {
         // Act
         // This runs the loop. It should read 'q', call on_event,
         // receive (), call update, receive Command::Quit, and return Ok.
-        let res = run_app(app, terminal, input);
+        let res = run_app(
+            app,
+            terminal,
+            input,
+            &MockClock::new(),
+            &RunOptions::default(),
+        );
 
         // Assert
         assert!(res.is_ok());
     }
+
+    #[test]
+    fn test_profile_logs_frame_timings() {
+        let mock = MockSystem::new();
+        mock.push_input(b"q");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let options = RunOptions::new().profile(true);
+
+        let lines = logger::mocks::capture(|| {
+            run_app(TestApp, terminal, Input::new(), &MockClock::new(), &options).unwrap();
+        });
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("frame: draw="));
+        assert!(lines[0].contains("diff="));
+        assert!(lines[0].contains("write="));
+        assert!(lines[0].contains("bytes="));
+    }
+
+    #[test]
+    fn test_no_profile_logs_nothing() {
+        let mock = MockSystem::new();
+        mock.push_input(b"q");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        let lines = logger::mocks::capture(|| {
+            run_app(
+                TestApp,
+                terminal,
+                Input::new(),
+                &MockClock::new(),
+                &RunOptions::default(),
+            )
+            .unwrap();
+        });
+
+        assert!(lines.is_empty());
+    }
+
+    struct InjectApp {
+        seen: Rc<RefCell<Vec<char>>>,
+    }
+
+    impl Application for InjectApp {
+        type Action = char;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, c: Self::Action) -> Command {
+            self.seen.borrow_mut().push(c);
+            match c {
+                'a' => Command::Inject(Event::Key(KeyEvent::new(KeyCode::Char('q')))),
+                _ => Command::Quit,
+            }
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_injected_event_reaches_update() {
+        let mock = MockSystem::new();
+        mock.push_input(b"a");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let app = InjectApp { seen: seen.clone() };
+
+        run_app(
+            app,
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(*seen.borrow(), vec!['a', 'q']);
+    }
+
+    struct ResizeApp {
+        window_size: Arc<Mutex<Option<(u16, u16)>>>,
+        resized_to: Rc<RefCell<Option<(u16, u16)>>>,
+        /// The mock's resize signal flag, raised along with the new size.
+        signal: Option<Arc<AtomicBool>>,
+    }
+
+    impl Application for ResizeApp {
+        type Action = Event;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            Some(event)
+        }
+
+        fn update(&mut self, event: Self::Action) -> Command {
+            match event {
+                Event::Resize(w, h) => {
+                    *self.resized_to.borrow_mut() = Some((w, h));
+                    Command::Quit
+                }
+                _ => {
+                    // Simulate the user resizing the window.
+                    *self.window_size.lock().unwrap() = Some((100, 30));
+                    if let Some(signal) = &self.signal {
+                        signal.store(true, Ordering::Relaxed);
+                    }
+                    Command::None
+                }
+            }
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_resize_event_delivered() {
+        let mock = MockSystem::new();
+        mock.push_input(b"a");
+        let app = ResizeApp {
+            window_size: mock.window_size.clone(),
+            resized_to: Rc::new(RefCell::new(None)),
+            signal: None,
+        };
+        let resized_to = app.resized_to.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        run_app(
+            app,
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(*resized_to.borrow(), Some((100, 30)));
+    }
+
+    #[test]
+    fn test_resize_signal_queries_size_once_resized() {
+        let mock = MockSystem::new().with_resize_signal();
+        let log_ref = mock.log.clone();
+        mock.push_input(b"a");
+        let app = ResizeApp {
+            window_size: mock.window_size.clone(),
+            resized_to: Rc::new(RefCell::new(None)),
+            signal: mock.resized.clone(),
+        };
+        let resized_to = app.resized_to.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        run_app(
+            app,
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        // Once at startup and once after the signal, not on every frame.
+        assert_eq!(*resized_to.borrow(), Some((100, 30)));
+        let log = log_ref.lock().unwrap();
+        let queries = log
+            .iter()
+            .filter(|l| l.starts_with("get_window_size"))
+            .count();
+        assert_eq!(queries, 2);
+    }
+
+    struct StartupApp;
+
+    impl Application for StartupApp {
+        type Action = ();
+
+        fn init(&self) -> Command {
+            Command::Batch(vec![
+                Command::SetTitle("Phosphor".to_string()),
+                Command::Tick(Duration::from_millis(30)),
+            ])
+        }
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            (event == Event::Tick).then_some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            Command::Quit
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            frame.write_str(0, 0, "drawn");
+        }
+    }
+
+    #[test]
+    fn test_init_batch_applies_all_commands() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let clock = MockClock::new();
+
+        run_app(
+            StartupApp,
+            terminal,
+            Input::new(),
+            &clock,
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        let log = log_ref.lock().unwrap();
+        let title = log
+            .iter()
+            .position(|s| s == "write(100, \"\x1b]0;Phosphor\x07\")")
+            .expect("title not written");
+        let render = log
+            .iter()
+            .position(|s| s.contains("\x1b[1;1H"))
+            .expect("frame not rendered");
+        assert!(title < render);
+        // The tick quit the app once its 30ms deadline passed.
+        assert!(clock.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_batch_stops_at_quit() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let clock = MockClock::new();
+        let mut runtime = Runtime::new(&terminal, &clock);
+        let inject = Command::Inject(Event::Tick);
+
+        let quit = runtime
+            .execute(Command::Batch(vec![Command::Quit, inject]))
+            .unwrap();
+
+        assert!(quit);
+        assert!(runtime.injected.is_empty());
+    }
+
+    struct BatchApp {
+        seen: Rc<RefCell<Vec<char>>>,
+    }
+
+    impl Application for BatchApp {
+        type Action = char;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Action) -> Command {
+            self.seen.borrow_mut().push(msg);
+            match msg {
+                'q' => Command::Batch(vec![Command::None, Command::Batch(vec![Command::Quit])]),
+                _ => Command::Batch(vec![Command::None, Command::None]),
+            }
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_batch_of_none_keeps_running_until_quit() {
+        let mock = MockSystem::new();
+        mock.push_input(b"abqz");
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        run_app(
+            BatchApp { seen: seen.clone() },
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        // The nested Quit in the 'q' batch ended the loop before 'z'.
+        assert_eq!(*seen.borrow(), vec!['a', 'b', 'q']);
+    }
+
+    struct CounterApp {
+        count: u32,
+    }
+
+    impl Application for CounterApp {
+        type Action = ();
+
+        fn init(&self) -> Command {
+            Command::Inject(Event::Tick)
+        }
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            (event == Event::Tick).then_some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            self.count += 1;
+            Command::Inject(Event::Tick)
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            frame.write_str(0, 0, &self.count.to_string());
+        }
+    }
+
+    #[test]
+    fn test_run_headless_captures_frames() {
+        let frames = run_headless(CounterApp { count: 0 }, TestBackend::new(4, 1), 3).unwrap();
+
+        let symbols: Vec<char> = frames.iter().map(|f| f.get(0, 0).symbol).collect();
+        assert_eq!(symbols, vec!['0', '1', '2']);
+        assert_eq!((frames[0].width, frames[0].height), (4, 1));
+    }
+
+    struct CounterView {
+        value: i32,
+    }
+
+    impl Application for CounterView {
+        type Action = ();
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            Command::None
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            use crate::widgets::{Block, Borders, Text};
+
+            let block = Block::new()
+                .borders(Borders::ALL)
+                .title("Hello")
+                .style(Style::new().fg(Color::Magenta));
+            let inner = frame.render_block(block, frame.area());
+            frame.render_widget(Text::new(format!("Count: {}", self.value)), inner);
+        }
+    }
+
+    #[test]
+    fn test_render_to_string_counter_view() {
+        let out = render_to_string(&CounterView { value: 3 }, 20, 3);
+
+        assert!(out.contains(" Hello "));
+        // Blank cells are skipped, so the digit is its own run.
+        assert!(out.contains("Count:"));
+        assert!(out.contains("\x1b[2;9H"));
+        assert!(out.contains(&Style::new().fg(Color::Magenta).to_ansi()));
+        assert!(out.starts_with("\x1b[1;1H"));
+        assert!(out.ends_with("\x1b[0m"));
+        // Nothing clears or otherwise drives a real screen.
+        assert!(!out.contains("\x1b[2J"));
+        assert!(!out.contains("\x1b[?"));
+    }
+
+    struct BackgroundApp;
+
+    impl Application for BackgroundApp {
+        type Action = ();
+
+        fn init(&self) -> Command {
+            Command::SetBackground(Color::Rgb(0x12, 0x34, 0x56))
+        }
+
+        fn on_event(&self, _event: Event) -> Option<Self::Action> {
+            Some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            Command::Quit
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_set_background_restored_on_quit() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        mock.push_input(b"q");
+
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        run_app(
+            BackgroundApp,
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        let log = log_ref.lock().unwrap();
+        let set = log
+            .iter()
+            .position(|s| s == "write(100, \"\x1b]11;#123456\x07\")")
+            .expect("OSC 11 not written");
+        let reset = log
+            .iter()
+            .position(|s| s == "write(100, \"\x1b]111\x07\")")
+            .expect("OSC 111 not written");
+        assert!(set < reset);
+    }
+
+    struct CursorApp {
+        show: bool,
+    }
+
+    impl Application for CursorApp {
+        type Action = char;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Action) -> Command {
+            match msg {
+                'q' => Command::Quit,
+                _ => {
+                    self.show = false;
+                    Command::None
+                }
+            }
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            if self.show {
+                frame.render_area(Rect::new(2, 1, 5, 1), |f| f.set_cursor(1, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_frame_cursor_shown_then_hidden() {
+        // One byte per read, so a frame is drawn between the two keys.
+        let mock = MockSystem::new().with_max_read(1);
+        let log_ref = mock.log.clone();
+        mock.push_input(b"aq");
+
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        run_app(
+            CursorApp { show: true },
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        let log = log_ref.lock().unwrap();
+        // The cursor is parked and shown at the end of the first frame, then
+        // hidden by the frame that no longer requests it.
+        let shown = log
+            .iter()
+            .position(|l| l.ends_with("\x1b[2;4H\x1b[?25h\x1b[?2026l\")"))
+            .expect("cursor not shown");
+        let hidden = log
+            .iter()
+            .rposition(|l| l.starts_with("write(100, \"\x1b[?2026h\x1b[?25l"))
+            .expect("cursor not hidden");
+        assert!(shown < hidden);
+    }
+
+    struct PanicApp;
+
+    impl Application for PanicApp {
+        type Action = ();
+
+        fn on_event(&self, _event: Event) -> Option<Self::Action> {
+            Some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            panic!("update failed");
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_restore_on_panic_writes_restore_sequence() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        mock.push_input(b"x");
+
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            run_app(
+                PanicApp,
+                terminal,
+                Input::new(),
+                &MockClock::new(),
+                &RunOptions::new().restore_on_panic(true),
+            )
+        }));
+        assert!(result.is_err());
+
+        // The hook's single combined write, separate from the writes `Drop`
+        // makes while unwinding.
+        let log = log_ref.lock().unwrap();
+        let restore = log
+            .iter()
+            .position(|l| l == "write(100, \"\x1b[?1000l\x1b[?1004l\x1b[?1049l\x1b[?25h\")")
+            .expect("restore sequence not written");
+        assert_eq!(log[restore + 1], "disable_raw(100)");
+    }
+
+    struct TimerApp {
+        fired_at: Rc<RefCell<Vec<Instant>>>,
+    }
+
+    impl Application for TimerApp {
+        type Action = Instant;
+
+        fn subscriptions(&self) -> Vec<Subscription<'_, Self::Action>> {
+            vec![Subscription::interval(Duration::from_millis(100), |now| {
+                now
+            })]
+        }
+
+        fn update(&mut self, now: Self::Action) -> Command {
+            let mut fired_at = self.fired_at.borrow_mut();
+            fired_at.push(now);
+            if fired_at.len() == 3 {
+                return Command::Quit;
+            }
+            Command::None
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    struct TickApp {
+        ticks: usize,
+    }
+
+    impl Application for TickApp {
+        type Action = ();
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            (event == Event::Tick).then_some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            self.ticks += 1;
+            if self.ticks == 3 {
+                return Command::Quit;
+            }
+            Command::None
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_tick_rate_delivers_ticks_without_input() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let clock = MockClock::new();
+        let options = RunOptions::new().tick_rate(Duration::from_millis(50));
+
+        run_app(
+            TickApp { ticks: 0 },
+            terminal,
+            Input::new(),
+            &clock,
+            &options,
+        )
+        .unwrap();
+
+        // Three ticks, each at most one frame after its 50ms deadline.
+        let elapsed = clock.elapsed();
+        assert!(elapsed >= Duration::from_millis(150), "{:?}", elapsed);
+        assert!(
+            elapsed <= Duration::from_millis(150) + 3 * FRAME,
+            "{:?}",
+            elapsed
+        );
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum SpanAction {
+        Tick,
+        Stop,
+    }
+
+    /// Counts ticks until a one-second interval subscription stops it.
+    struct TickSpanApp {
+        ticks: Rc<RefCell<u32>>,
+    }
+
+    impl Application for TickSpanApp {
+        type Action = SpanAction;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            (event == Event::Tick).then_some(SpanAction::Tick)
+        }
+
+        fn subscriptions(&self) -> Vec<Subscription<'_, Self::Action>> {
+            vec![Subscription::interval(Duration::from_secs(1), |_| {
+                SpanAction::Stop
+            })]
+        }
+
+        fn update(&mut self, msg: Self::Action) -> Command {
+            match msg {
+                SpanAction::Tick => {
+                    *self.ticks.borrow_mut() += 1;
+                    Command::None
+                }
+                SpanAction::Stop => Command::Quit,
+            }
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_tick_rate_count_over_span() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let clock = MockClock::new();
+        let ticks = Rc::new(RefCell::new(0));
+        let options = RunOptions::new().tick_rate(Duration::from_millis(50));
+
+        run_app(
+            TickSpanApp {
+                ticks: ticks.clone(),
+            },
+            terminal,
+            Input::new(),
+            &clock,
+            &options,
+        )
+        .unwrap();
+
+        // Each tick lands at most one ~17ms frame late, so over one second
+        // the period is between 50ms and 67ms.
+        let ticks = *ticks.borrow();
+        assert!((1000 / 67..=1000 / 50).contains(&ticks), "{ticks} ticks");
+    }
+
+    #[test]
+    fn test_interval_subscription_cadence() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let clock = MockClock::new();
+        let fired_at = Rc::new(RefCell::new(Vec::new()));
+        let app = TimerApp {
+            fired_at: fired_at.clone(),
+        };
+
+        run_app(app, terminal, Input::new(), &clock, &RunOptions::default()).unwrap();
+
+        // Each loop iteration advances the mock clock by one frame, so every
+        // firing lands within one frame after its 100ms deadline.
+        let fired_at = fired_at.borrow();
+        assert_eq!(fired_at.len(), 3);
+        for (i, at) in fired_at.iter().enumerate() {
+            let expected = Duration::from_millis(100 * (i as u64 + 1));
+            let offset = *at - clock.start();
+            assert!(offset >= expected, "fired early: {:?}", offset);
+            assert!(offset < expected + FRAME);
+        }
+    }
+
+    /// Counts its draws until a one-second interval subscription stops it.
+    struct DrawCountApp {
+        draws: Rc<Cell<u32>>,
+    }
+
+    impl Application for DrawCountApp {
+        type Action = ();
+
+        fn subscriptions(&self) -> Vec<Subscription<'_, Self::Action>> {
+            vec![Subscription::interval(Duration::from_secs(1), |_| ())]
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            Command::Quit
+        }
+
+        fn draw(&self, _frame: &mut Frame) {
+            self.draws.set(self.draws.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_frame_rate_sets_frame_budget() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let clock = MockClock::new();
+        let draws = Rc::new(Cell::new(0));
+        let app = DrawCountApp {
+            draws: draws.clone(),
+        };
+        let options = RunOptions::new().frame_rate(10);
+
+        run_app(app, terminal, Input::new(), &clock, &options).unwrap();
+
+        // One 100ms frame per iteration: ten frames fill the second.
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+        assert_eq!(draws.get(), 11);
+    }
+
+    #[test]
+    fn test_lazy_redraw_skips_idle_frames() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let draws = Rc::new(Cell::new(0));
+        let app = DrawCountApp {
+            draws: draws.clone(),
+        };
+        let options = RunOptions::new().lazy_redraw(true);
+
+        run_app(app, terminal, Input::new(), &MockClock::new(), &options).unwrap();
+
+        // Only the first frame: no action ran before the app quit.
+        assert_eq!(draws.get(), 1);
+    }
+
+    /// Draws an RGB-colored cell and a plain one, then quits.
+    struct RgbApp;
+
+    impl Application for RgbApp {
+        type Action = ();
+
+        fn init(&self) -> Command {
+            Command::Inject(Event::Tick)
+        }
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            (event == Event::Tick).then_some(())
+        }
+
+        fn update(&mut self, _msg: Self::Action) -> Command {
+            Command::Quit
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            frame.write_str_with_style(0, 0, "x", Style::fg_color(Color::Rgb(255, 0, 0)));
+            frame.write_str(1, 0, "y");
+        }
+    }
+
+    fn frame_output(options: &RunOptions) -> String {
+        let mock = MockSystem::new();
+        let log = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        run_app(RgbApp, terminal, Input::new(), &MockClock::new(), options).unwrap();
+
+        let log = log.lock().unwrap();
+        log.iter()
+            .find(|entry| entry.contains('x'))
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_truecolor_option_downsamples_rgb() {
+        assert!(frame_output(&RunOptions::new()).contains("38;2;255;0;0"));
+
+        let downsampled = frame_output(&RunOptions::new().truecolor(false));
+        assert!(!downsampled.contains("38;2;"));
+        assert!(downsampled.contains(&Color::Rgb(255, 0, 0).to_256().to_ansi_fg()));
+    }
+
+    #[test]
+    fn test_coalesce_runs_option_reaches_renderer() {
+        // The per-cell encoder carries on printing after a style change; the
+        // run encoder starts a new run with its own cursor move.
+        assert!(!frame_output(&RunOptions::new()).contains("\x1b[1;2H"));
+        assert!(frame_output(&RunOptions::new().coalesce_runs(true)).contains("\x1b[1;2H"));
+    }
+
+    /// Swallows every key without changing what it draws.
+    struct StaticApp {
+        draws: Rc<Cell<u32>>,
+    }
+
+    impl Application for StaticApp {
+        type Action = char;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Action) -> Command {
+            match msg {
+                'q' => Command::Quit,
+                _ => Command::NoRedraw,
+            }
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            self.draws.set(self.draws.get() + 1);
+            frame.write_str(0, 0, "static");
+        }
+    }
+
+    #[test]
+    fn test_no_redraw_keeps_writes_flat() {
+        // One byte per read, so each key gets its own loop iteration.
+        let mock = MockSystem::new().with_max_read(1);
+        let log_ref = mock.log.clone();
+        mock.push_input(b"abcq");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let draws = Rc::new(Cell::new(0));
+        let app = StaticApp {
+            draws: draws.clone(),
+        };
+        let options = RunOptions::new().lazy_redraw(true);
+
+        run_app(app, terminal, Input::new(), &MockClock::new(), &options).unwrap();
+
+        // Only the first frame is drawn and written; nothing is written
+        // while the following keys are read.
+        assert_eq!(draws.get(), 1);
+        let log = log_ref.lock().unwrap();
+        let frame = log
+            .iter()
+            .position(|l| l.contains("static"))
+            .expect("frame not written");
+        let last_read = log.iter().rposition(|l| l.starts_with("read(")).unwrap();
+        assert!(
+            log[frame + 1..last_read]
+                .iter()
+                .all(|l| !l.starts_with("write(")),
+            "{:?}",
+            &log[frame..]
+        );
+    }
+
+    /// Records the kind of every click until it sees a double click.
+    struct ClickApp {
+        clicks: Rc<RefCell<Vec<MouseKind>>>,
+    }
+
+    impl Application for ClickApp {
+        type Action = MouseKind;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Mouse(mouse) => Some(mouse.kind),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, kind: Self::Action) -> Command {
+            let done = kind == MouseKind::DoubleClick;
+            self.clicks.borrow_mut().push(kind);
+            if done { Command::Quit } else { Command::None }
+        }
+
+        fn draw(&self, _frame: &mut Frame) {}
+    }
+
+    #[test]
+    fn test_double_click_option_promotes_clicks() {
+        let mock = MockSystem::new();
+        // Two presses at the same cell, read in the same frame.
+        mock.push_input(b"\x1b[M !!\x1b[M !!");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let clicks = Rc::new(RefCell::new(Vec::new()));
+        let app = ClickApp {
+            clicks: clicks.clone(),
+        };
+        let options = RunOptions::new().double_click(Duration::from_millis(300));
+
+        run_app(app, terminal, Input::new(), &MockClock::new(), &options).unwrap();
+
+        assert_eq!(
+            *clicks.borrow(),
+            [MouseKind::LeftClick, MouseKind::DoubleClick]
+        );
+    }
+
+    /// Asks for a full repaint on `r`.
+    struct RedrawApp;
+
+    impl Application for RedrawApp {
+        type Action = char;
+
+        fn on_event(&self, event: Event) -> Option<Self::Action> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) => Some(c),
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, msg: Self::Action) -> Command {
+            match msg {
+                'r' => Command::Redraw,
+                _ => Command::Quit,
+            }
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            frame.write_str(0, 0, "same");
+        }
+    }
+
+    #[test]
+    fn test_redraw_command_repaints_unchanged_frame() {
+        let mock = MockSystem::new().with_max_read(1);
+        let log_ref = mock.log.clone();
+        mock.push_input(b"rq");
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        run_app(
+            RedrawApp,
+            terminal,
+            Input::new(),
+            &MockClock::new(),
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+        // The first frame and the repaint both write the text.
+        let log = log_ref.lock().unwrap();
+        let frames: Vec<_> = log.iter().filter(|l| l.contains("same")).collect();
+        assert_eq!(frames.len(), 2);
+        assert!(frames[1].contains("\x1b[2J"));
+    }
 }
@@ -18,11 +18,46 @@
 use std::fs::File;
 use std::io::{self, Write};
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The open log file and how lines are written to it.
+struct Logger {
+    file: File,
+    timestamps: bool,
+}
 
 /// Global singleton for the log file handle.
 ///
 /// We use a `Mutex` to ensure safe concurrent access from multiple threads.
-static LOGGER: Mutex<Option<File>> = Mutex::new(None);
+static LOGGER: Mutex<Option<Logger>> = Mutex::new(None);
+
+/// Options for [`init_with_options`].
+#[derive(Debug, Clone)]
+pub struct LogOptions {
+    timestamps: bool,
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self { timestamps: true }
+    }
+}
+
+impl LogOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether each line starts with a UTC timestamp such as
+    /// `2024-01-02T15:04:05.123` (default: true).
+    ///
+    /// Turn it off for output that is identical from run to run.
+    pub fn timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+}
 
 /// Initializes the logger, creating (or truncating) `debug.log`.
 ///
@@ -31,21 +66,78 @@ static LOGGER: Mutex<Option<File>> = Mutex::new(None);
 /// # Errors
 /// Returns an [`io::Error`] if the file cannot be created.
 pub fn init() -> io::Result<()> {
+    init_with_options(LogOptions::default())
+}
+
+/// Like [`init`], with custom [`LogOptions`].
+///
+/// # Errors
+/// Returns an [`io::Error`] if the file cannot be created.
+pub fn init_with_options(options: LogOptions) -> io::Result<()> {
     let file = File::create("debug.log")?;
     let mut guard = LOGGER.lock().unwrap();
-    *guard = Some(file);
+    *guard = Some(Logger {
+        file,
+        timestamps: options.timestamps,
+    });
     Ok(())
 }
 
+/// Formats `time` as `YYYY-MM-DDTHH:MM:SS.mmm` in UTC.
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Builds the line written to the log file for `msg`.
+fn format_line(msg: &str, timestamps: bool, now: SystemTime) -> String {
+    if timestamps {
+        format!("{} {}", timestamp(now), msg)
+    } else {
+        msg.to_string()
+    }
+}
+
 /// Internal function to write a formatted string to the log file.
 ///
 /// Prefer using the [`crate::log!`] macro instead of calling this directly.
 pub fn write_log(msg: &str) {
+    #[cfg(test)]
+    mocks::CAPTURE.with(|capture| {
+        if let Some(lines) = capture.borrow_mut().as_mut() {
+            lines.push(msg.to_string());
+        }
+    });
+
     if let Ok(mut guard) = LOGGER.lock()
-        && let Some(file) = guard.as_mut()
+        && let Some(logger) = guard.as_mut()
     {
+        let line = format_line(msg, logger.timestamps, SystemTime::now());
         // We ignore write errors to prevent panics in the logging infrastructure
-        let _ = writeln!(file, "{}", msg);
+        let _ = writeln!(logger.file, "{}", line);
     }
 }
 
@@ -65,10 +157,30 @@ macro_rules! log {
     };
 }
 
+#[cfg(test)]
+pub(crate) mod mocks {
+    use std::cell::RefCell;
+
+    thread_local! {
+        pub(super) static CAPTURE: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    }
+
+    /// Runs `f` and returns every line logged on this thread while it ran.
+    ///
+    /// Capture is per-thread, so tests running in parallel don't see each
+    /// other's lines.
+    pub fn capture<F: FnOnce()>(f: F) -> Vec<String> {
+        CAPTURE.with(|capture| *capture.borrow_mut() = Some(Vec::new()));
+        f();
+        CAPTURE.with(|capture| capture.borrow_mut().take().unwrap_or_default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
+    use std::time::Duration;
 
     #[test]
     fn test_logging() {
@@ -91,4 +203,31 @@ mod tests {
         // Cleanup
         let _ = fs::remove_file("debug.log");
     }
+
+    #[test]
+    fn test_format_line_without_timestamps() {
+        assert_eq!(
+            format_line("frame drawn", false, SystemTime::now()),
+            "frame drawn"
+        );
+    }
+
+    #[test]
+    fn test_format_line_with_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_704_207_845_123);
+        assert_eq!(
+            format_line("frame drawn", true, time),
+            "2024-01-02T15:04:05.123 frame drawn"
+        );
+
+        // Any time gets the same `YYYY-MM-DDTHH:MM:SS.mmm ` shape.
+        let line = format_line("x", true, SystemTime::now());
+        let digits = [0..4, 5..7, 8..10, 11..13, 14..16, 17..19, 20..23];
+        assert!(
+            digits
+                .into_iter()
+                .all(|r| line[r].bytes().all(|b| b.is_ascii_digit()))
+        );
+        assert_eq!(&line[23..], " x");
+    }
 }
@@ -3,54 +3,271 @@
 //! It uses a [`Buffer`] to track the current state of the
 //! screen and only sends the minimal set of ANSI escape codes to update it.
 
-use crate::buffer::Buffer;
+use crate::buffer::{Buffer, char_width};
+use crate::clock::{Clock, SystemClock};
+use crate::style::Style;
 use crate::terminal::Terminal;
+use std::fmt::Write;
 use std::io;
+use std::time::Duration;
+
+/// Timings and output size of a single [`Renderer`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Time spent diffing and encoding the frame.
+    pub diff: Duration,
+    /// Time spent writing to the terminal.
+    pub write: Duration,
+    /// Number of bytes written.
+    pub bytes: usize,
+}
+
+/// Produces the ANSI sequence that updates a screen showing `prev` so that it
+/// shows `next`.
+///
+/// This is the cell diff that [`Renderer::render`] writes, without needing a
+/// [`Terminal`]. The renderer adds its own framing around it: the
+/// synchronized output brackets (`ESC [ ? 2026 h` ... `l`), hiding and
+/// showing the cursor, and, with [`Renderer::coalesce_runs`], the encoding of
+/// [`render_runs_to_string`] instead. If the buffers differ in size, the
+/// sequence starts by clearing the screen and redraws every cell.
+///
+/// Within a run of adjacent changed cells on a row, the cursor is only moved
+/// for the first cell (printing advances it), and an SGR sequence is only
+/// written when a cell's style differs from the one before it.
+pub fn render_diff_to_string(prev: &Buffer, next: &Buffer) -> String {
+    encode_diff(prev, next, false)
+}
+
+/// [`render_diff_to_string`], optionally downsampling colors with
+/// [`Style::to_256`].
+fn encode_diff(prev: &Buffer, next: &Buffer, downsample: bool) -> String {
+    let mut out = String::new();
+
+    // If buffers sizes are different, clear the screen
+    if next.width != prev.width || next.height != prev.height {
+        out.push_str("\x1b[2J");
+    }
+
+    // Where the cursor sits after the last write, and the style it used; a
+    // jump elsewhere forgets it.
+    let mut last: Option<(u16, u16, Style)> = None;
+    for change in next.diff(prev) {
+        // Printing the wide char to the left already covered this cell.
+        if change.cell.is_continuation() {
+            continue;
+        }
+        let style = change.cell.style;
+        let contiguous = matches!(last, Some((x, y, _)) if y == change.y && x == change.x);
+        if !contiguous {
+            let _ = write!(out, "\x1b[{};{}H", change.y + 1, change.x + 1);
+        }
+        if !(contiguous && last.is_some_and(|(.., s)| s == style)) {
+            out.push_str(&sgr(style, downsample));
+        }
+        out.push(change.cell.symbol);
+        last = Some((change.x + char_width(change.cell.symbol), change.y, style));
+    }
+
+    out
+}
+
+/// Like [`render_diff_to_string`], but groups consecutive changed cells on a
+/// row that share a style into a single run.
+///
+/// Each run costs one cursor move and one SGR sequence followed by all of its
+/// characters, instead of a move and an SGR per cell.
+pub fn render_runs_to_string(prev: &Buffer, next: &Buffer) -> String {
+    encode_runs(prev, next, false)
+}
+
+/// [`render_runs_to_string`], optionally downsampling colors with
+/// [`Style::to_256`].
+fn encode_runs(prev: &Buffer, next: &Buffer, downsample: bool) -> String {
+    let mut out = String::new();
+
+    if next.width != prev.width || next.height != prev.height {
+        out.push_str("\x1b[2J");
+    }
+
+    for span in next.diff_spans(prev, true) {
+        // Printing the wide char to the left already covered these cells.
+        let Some(skip) = span.cells.iter().position(|cell| !cell.is_continuation()) else {
+            continue;
+        };
+        let cells = &span.cells[skip..];
+        let _ = write!(out, "\x1b[{};{}H", span.y + 1, span.x + skip as u16 + 1);
+        out.push_str(&sgr(cells[0].style, downsample));
+        out.extend(
+            cells
+                .iter()
+                .filter(|cell| !cell.is_continuation())
+                .map(|cell| cell.symbol),
+        );
+    }
+
+    out
+}
+
+fn sgr(style: Style, downsample: bool) -> String {
+    if downsample {
+        style.to_256().to_ansi()
+    } else {
+        style.to_ansi()
+    }
+}
 
 /// The primary rendering engine.
 pub struct Renderer {
     /// The state of the terminal as of the last render.
     current_buffer: Buffer,
+    /// The [`Buffer::content_hash`] of `current_buffer`.
+    current_hash: u64,
+    /// Whether to encode frames with [`render_runs_to_string`].
+    coalesce_runs: bool,
+    /// Whether to bracket each frame in synchronized output escapes.
+    synchronized_output: bool,
+    /// Whether `Rgb` colors are written as-is rather than downsampled.
+    truecolor: bool,
+    /// Where the cursor was left visible by the last render, if anywhere.
+    shown_cursor: Option<(u16, u16)>,
+    /// Where the next render should leave the cursor visible, if anywhere.
+    cursor: Option<(u16, u16)>,
 }
 
 impl Renderer {
     /// Creates a new renderer for a terminal of the given size.
     pub fn new(width: u16, height: u16) -> Self {
+        let current_buffer = Buffer::new(width, height);
         Self {
-            current_buffer: Buffer::new(width, height),
+            current_hash: current_buffer.content_hash(),
+            current_buffer,
+            coalesce_runs: false,
+            synchronized_output: true,
+            truecolor: true,
+            shown_cursor: None,
+            cursor: None,
         }
     }
 
+    /// Sets whether adjacent changed cells with the same style are written as
+    /// a single run (see [`render_runs_to_string`]).
+    pub fn coalesce_runs(mut self, coalesce_runs: bool) -> Self {
+        self.coalesce_runs = coalesce_runs;
+        self
+    }
+
+    /// Sets whether each frame is wrapped in `ESC [ ? 2026 h` ... `ESC [ ? 2026 l`
+    /// (default: true).
+    ///
+    /// This DEC private mode asks the terminal to hold the screen until the
+    /// whole frame has arrived, so a repaint never shows half drawn.
+    /// Terminals that don't support it ignore the sequences.
+    pub fn with_synchronized_output(mut self, synchronized_output: bool) -> Self {
+        self.synchronized_output = synchronized_output;
+        self
+    }
+
+    /// Sets whether [`Color::Rgb`](crate::Color::Rgb) is written as 24-bit
+    /// color (default: true).
+    ///
+    /// Turn it off for terminals without truecolor support: RGB colors are
+    /// then mapped to the nearest of the 256 indexed colors on output (see
+    /// [`Color::to_256`](crate::Color::to_256)). The buffers keep the
+    /// original colors.
+    pub fn with_truecolor(mut self, truecolor: bool) -> Self {
+        self.truecolor = truecolor;
+        self
+    }
+
+    /// Sets where the cursor is shown after the following renders, or hides
+    /// it with `None` (see [`Frame::set_cursor`](crate::Frame::set_cursor)).
+    ///
+    /// While a cursor is shown, each frame is written between `ESC [ ? 25 l`
+    /// and `ESC [ ? 25 h`, and ends by moving the cursor back to `cursor`, so
+    /// the caret never visibly jumps across the cells being drawn.
+    pub fn set_cursor(&mut self, cursor: Option<(u16, u16)>) {
+        self.cursor = cursor;
+    }
+
+    /// Forgets what the terminal shows, so the next render clears the screen
+    /// and repaints every cell.
+    ///
+    /// Use this after something else wrote to the terminal behind the
+    /// renderer's back.
+    pub fn invalidate(&mut self) {
+        // A size mismatch makes the diff clear the screen and redraw everything.
+        self.current_buffer = Buffer::new(0, 0);
+        self.current_hash = self.current_buffer.content_hash();
+    }
+
     /// Updates the terminal to match the state of the given buffer.
     ///
     /// This method calculates the difference between the new buffer and the
     /// previous one, and only writes the changed cells to the terminal.
     pub fn render(&mut self, terminal: &Terminal, next: &Buffer) -> io::Result<()> {
-        // TODO: Implement diff-styling to only send ANSI style codes when they change.
-        // If buffers sizes are different, clear the screen
-        if next.width != self.current_buffer.width || next.height != self.current_buffer.height {
-            terminal.write("\x1b[2J".as_bytes())?;
-        }
+        self.render_with_stats(terminal, next, &SystemClock)
+            .map(|_| ())
+    }
+
+    /// Like [`render`](Self::render), but measures each phase with `clock`.
+    pub fn render_with_stats(
+        &mut self,
+        terminal: &Terminal,
+        next: &Buffer,
+        clock: &dyn Clock,
+    ) -> io::Result<RenderStats> {
+        let start = clock.now();
 
-        let diff = next.diff(&self.current_buffer);
+        // Nothing changed since the last frame: skip the diff entirely.
+        let next_hash = next.content_hash();
+        if next_hash == self.current_hash
+            && *next == self.current_buffer
+            && self.cursor == self.shown_cursor
+        {
+            return Ok(RenderStats {
+                diff: clock.now() - start,
+                ..RenderStats::default()
+            });
+        }
 
-        for change in diff {
-            terminal.write(format!("\x1b[{};{}H", change.y + 1, change.x + 1).as_bytes())?;
-            terminal.write(change.cell.style.to_ansi().as_bytes())?;
-            let mut buf = [0u8; 4];
-            terminal.write(change.cell.symbol.encode_utf8(&mut buf).as_bytes())?;
+        let downsample = !self.truecolor;
+        let mut output = if self.coalesce_runs {
+            encode_runs(&self.current_buffer, next, downsample)
+        } else {
+            encode_diff(&self.current_buffer, next, downsample)
+        };
+        // Printing moves the cursor, so hide it while drawing and park it
+        // where the app wants it afterwards.
+        if self.shown_cursor.is_some() {
+            output.insert_str(0, "\x1b[?25l");
+        }
+        if let Some((x, y)) = self.cursor {
+            let _ = write!(output, "\x1b[{};{}H\x1b[?25h", y + 1, x + 1);
+        }
+        if self.synchronized_output {
+            output.insert_str(0, "\x1b[?2026h");
+            output.push_str("\x1b[?2026l");
         }
+        let encoded = clock.now();
+        terminal.write_all(output.as_bytes())?;
 
-        self.current_buffer = next.clone();
+        self.current_buffer.clone_from(next);
+        self.current_hash = next_hash;
+        self.shown_cursor = self.cursor;
 
-        Ok(())
+        Ok(RenderStats {
+            diff: encoded - start,
+            write: clock.now() - encoded,
+            bytes: output.len(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Color, Style, terminal::mocks::MockSystem};
+    use crate::{Color, terminal::mocks::MockSystem};
 
     #[test]
     fn test_renderer_minimal_updates() {
@@ -75,4 +292,330 @@ mod tests {
         // Check for the style code: Reset(0), Red(31)
         assert!(log.iter().any(|s| s.contains("0;31")));
     }
+
+    #[test]
+    fn test_render_diff_to_string_single_cell() {
+        let prev = Buffer::new(3, 3);
+        let mut next = Buffer::new(3, 3);
+        next.set_with_style(1, 1, 'X', Style::new().fg(Color::Red));
+
+        let out = render_diff_to_string(&prev, &next);
+
+        let expected = format!("\x1b[2;2H{}X", Style::new().fg(Color::Red).to_ansi());
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_render_diff_to_string_unchanged_is_empty() {
+        let buffer = Buffer::new(3, 3);
+        assert_eq!(render_diff_to_string(&buffer, &buffer), "");
+    }
+
+    #[test]
+    fn test_render_diff_to_string_resize_clears() {
+        let prev = Buffer::new(2, 2);
+        let next = Buffer::new(3, 3);
+
+        let out = render_diff_to_string(&prev, &next);
+
+        // Every cell is redrawn, with one cursor move per row.
+        assert!(out.starts_with("\x1b[2J"));
+        assert_eq!(out.matches('H').count(), 3);
+        assert_eq!(out.matches(' ').count(), 9);
+    }
+
+    #[test]
+    fn test_render_diff_to_string_style_written_once_per_run() {
+        let prev = Buffer::new(6, 2);
+        let mut next = Buffer::new(6, 2);
+        let style = Style::new().fg(Color::Yellow);
+        for x in 0..6 {
+            next.set_with_style(x, 0, 'y', style);
+        }
+        next.set_with_style(3, 1, 'z', style);
+
+        let out = render_diff_to_string(&prev, &next);
+
+        // Once for the run on row 0, once more after the jump to row 1.
+        assert_eq!(out.matches(&style.to_ansi()).count(), 2);
+        assert_eq!(out.matches('y').count(), 6);
+    }
+
+    #[test]
+    fn test_render_diff_to_string_skips_contiguous_moves() {
+        let prev = Buffer::new(6, 1);
+        let mut next = Buffer::new(6, 1);
+        for (x, c) in [(1, 'a'), (2, 'b'), (3, 'c'), (5, 'e')] {
+            next.set(x, 0, c);
+        }
+
+        let out = render_diff_to_string(&prev, &next);
+
+        let style = Style::default().to_ansi();
+        assert_eq!(out, format!("\x1b[1;2H{style}abc\x1b[1;6H{style}e"));
+    }
+
+    #[test]
+    fn test_render_diff_to_string_wide_char() {
+        let prev = Buffer::new(4, 1);
+        let mut next = Buffer::new(4, 1);
+        next.set(0, 0, '世');
+        next.set(2, 0, 'a');
+
+        let out = render_diff_to_string(&prev, &next);
+
+        // The continuation cell isn't printed and doesn't break the run.
+        let style = Style::default().to_ansi();
+        assert_eq!(out, format!("\x1b[1;1H{style}世a"));
+    }
+
+    #[test]
+    fn test_render_runs_coalesces_styled_line() {
+        let prev = Buffer::new(12, 2);
+        let mut next = Buffer::new(12, 2);
+        let style = Style::new().fg(Color::Green);
+        for (i, c) in "abcdefghij".chars().enumerate() {
+            next.set_with_style(1 + i as u16, 1, c, style);
+        }
+
+        let out = render_runs_to_string(&prev, &next);
+
+        assert_eq!(out, format!("\x1b[2;2H{}abcdefghij", style.to_ansi()));
+    }
+
+    #[test]
+    fn test_render_runs_splits_on_style_and_gap() {
+        let prev = Buffer::new(6, 1);
+        let mut next = Buffer::new(6, 1);
+        let red = Style::new().fg(Color::Red);
+        next.set_with_style(0, 0, 'a', red);
+        next.set_with_style(1, 0, 'b', Style::new());
+        next.set_with_style(3, 0, 'c', red);
+        next.set_with_style(4, 0, 'd', red);
+
+        let out = render_runs_to_string(&prev, &next);
+
+        assert_eq!(out.matches('H').count(), 3);
+        assert!(out.ends_with(&format!("\x1b[1;4H{}cd", red.to_ansi())));
+    }
+
+    #[test]
+    fn test_renderer_short_writes_send_whole_frame() {
+        let mock = MockSystem::new().with_max_write(4);
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 1);
+        let writes = log_ref.lock().unwrap().len();
+
+        let mut next = Buffer::new(3, 1);
+        next.set(1, 0, 'x');
+        renderer.render(&terminal, &next).unwrap();
+
+        let sent: String = log_ref.lock().unwrap()[writes..]
+            .iter()
+            .map(|entry| &entry["write(100, \"".len()..entry.len() - 2])
+            .collect();
+        let mut expected = render_diff_to_string(&Buffer::new(3, 1), &next);
+        expected.insert_str(0, "\x1b[?2026h");
+        expected.push_str("\x1b[?2026l");
+        assert_eq!(sent, expected);
+    }
+
+    #[test]
+    fn test_renderer_single_write_per_frame() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(5, 3);
+        let writes = log_ref.lock().unwrap().len();
+
+        let mut next = Buffer::new(5, 3);
+        next.set(0, 0, 'a');
+        next.set(4, 1, 'b');
+        next.set_with_style(2, 2, 'c', Style::new().fg(Color::Green));
+        renderer.render(&terminal, &next).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert_eq!(log.len(), writes + 1);
+        let payload = &log[writes];
+        let a = payload.find("\x1b[1;1H").expect("move to (0, 0)");
+        let b = payload.find("\x1b[2;5H").expect("move to (4, 1)");
+        let c = payload.find("\x1b[3;3H").expect("move to (2, 2)");
+        assert!(a < b && b < c);
+        for symbol in ["a", "b", "c"] {
+            assert!(payload.contains(symbol));
+        }
+    }
+
+    #[test]
+    fn test_renderer_downsamples_without_truecolor() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut next = Buffer::new(2, 1);
+        next.set_with_style(0, 0, 'z', Style::new().fg(Color::Rgb(255, 0, 0)));
+
+        let mut renderer = Renderer::new(2, 1)
+            .with_synchronized_output(false)
+            .with_truecolor(false);
+        renderer.render(&terminal, &next).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        let written = log.last().unwrap();
+        assert!(written.contains("38;5;196"));
+        assert!(!written.contains("38;2;"));
+    }
+
+    #[test]
+    fn test_renderer_synchronized_output() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut next = Buffer::new(2, 1);
+        next.set(0, 0, 'z');
+
+        Renderer::new(2, 1).render(&terminal, &next).unwrap();
+        Renderer::new(2, 1)
+            .with_synchronized_output(false)
+            .render(&terminal, &next)
+            .unwrap();
+
+        let log = log_ref.lock().unwrap();
+        let payload = render_diff_to_string(&Buffer::new(2, 1), &next);
+        let synced = &log[log.len() - 2];
+        assert_eq!(
+            *synced,
+            format!("write(100, \"\x1b[?2026h{payload}\x1b[?2026l\")")
+        );
+        assert_eq!(log[log.len() - 1], format!("write(100, \"{payload}\")"));
+    }
+
+    #[test]
+    fn test_renderer_coalesce_runs_single_write() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(10, 1).coalesce_runs(true);
+        let writes = log_ref.lock().unwrap().len();
+
+        let mut next = Buffer::new(10, 1);
+        for x in 0..10 {
+            next.set_with_style(x, 0, 'x', Style::new().fg(Color::Blue));
+        }
+        renderer.render(&terminal, &next).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert_eq!(log.len(), writes + 1);
+        assert!(log[writes].contains("xxxxxxxxxx"));
+    }
+
+    #[test]
+    fn test_renderer_reuses_buffer_allocation() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let mut renderer = Renderer::new(4, 2);
+        let ptr = renderer.current_buffer.content.as_ptr();
+
+        for c in ['a', 'b', 'c'] {
+            let mut next = Buffer::new(4, 2);
+            next.set(0, 0, c);
+            renderer.render(&terminal, &next).unwrap();
+            assert_eq!(renderer.current_buffer.content.as_ptr(), ptr);
+        }
+        assert_eq!(renderer.current_buffer.get(0, 0).symbol, 'c');
+    }
+
+    #[test]
+    fn test_renderer_skips_unchanged_frame() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 3);
+
+        let mut next = Buffer::new(3, 3);
+        next.set(0, 0, 'A');
+        renderer.render(&terminal, &next).unwrap();
+        let writes = log_ref.lock().unwrap().len();
+
+        renderer.render(&terminal, &next).unwrap();
+        assert_eq!(log_ref.lock().unwrap().len(), writes);
+    }
+
+    #[test]
+    fn test_renderer_brackets_frame_only_with_cursor() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 1).with_synchronized_output(false);
+        let frame = |c| {
+            let mut buffer = Buffer::new(3, 1);
+            buffer.set(0, 0, c);
+            buffer
+        };
+
+        // No cursor requested: the diff is written on its own.
+        renderer.render(&terminal, &frame('a')).unwrap();
+        let payload = render_diff_to_string(&Buffer::new(3, 1), &frame('a'));
+        assert_eq!(
+            log_ref.lock().unwrap().last().unwrap(),
+            &format!("write(100, \"{payload}\")")
+        );
+
+        // The first frame with a cursor shows it at the end; later frames
+        // hide it while drawing.
+        renderer.set_cursor(Some((2, 0)));
+        renderer.render(&terminal, &frame('b')).unwrap();
+        renderer.render(&terminal, &frame('c')).unwrap();
+        let log = log_ref.lock().unwrap();
+        let style = Style::default().to_ansi();
+        assert_eq!(
+            log[log.len() - 2],
+            format!("write(100, \"\x1b[1;1H{style}b\x1b[1;3H\x1b[?25h\")")
+        );
+        assert_eq!(
+            log[log.len() - 1],
+            format!("write(100, \"\x1b[?25l\x1b[1;1H{style}c\x1b[1;3H\x1b[?25h\")")
+        );
+    }
+
+    #[test]
+    fn test_renderer_moves_cursor_without_changes() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 1).with_synchronized_output(false);
+        let buffer = Buffer::new(3, 1);
+
+        renderer.set_cursor(Some((1, 0)));
+        renderer.render(&terminal, &buffer).unwrap();
+        renderer.set_cursor(None);
+        renderer.render(&terminal, &buffer).unwrap();
+        let writes = log_ref.lock().unwrap().len();
+        renderer.render(&terminal, &buffer).unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert_eq!(log.len(), writes);
+        assert_eq!(log[writes - 2], "write(100, \"\x1b[1;2H\x1b[?25h\")");
+        assert_eq!(log[writes - 1], "write(100, \"\x1b[?25l\")");
+    }
+
+    #[test]
+    fn test_renderer_invalidate_repaints_everything() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let terminal = Terminal::new_with_system(Box::new(mock)).unwrap();
+        let mut renderer = Renderer::new(3, 2).with_synchronized_output(false);
+        let mut next = Buffer::new(3, 2);
+        next.set(1, 0, 'A');
+        renderer.render(&terminal, &next).unwrap();
+
+        renderer.invalidate();
+        renderer.render(&terminal, &next).unwrap();
+
+        // The same output as a first render onto a screen of another size.
+        let log = log_ref.lock().unwrap();
+        let payload = render_diff_to_string(&Buffer::new(0, 0), &next);
+        assert!(payload.starts_with("\x1b[2J"));
+        assert_eq!(payload.matches(' ').count(), 5);
+        assert_eq!(*log.last().unwrap(), format!("write(100, \"{payload}\")"));
+    }
 }
@@ -2,8 +2,11 @@
 //!
 //! It supports ANSI colors and text modifiers like Bold, Italic, and Underline.
 
+use std::fmt;
+use std::str::FromStr;
+
 /// Represents a color in the terminal.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
     Reset,
     Black,
@@ -29,7 +32,9 @@ pub enum Color {
 impl Color {
     pub fn from_hex(hex: &str) -> Option<Self> {
         let s = hex.strip_prefix("#").unwrap_or(hex);
-        if s.len() != 6 {
+        // Checking the digits up front keeps the slices on char boundaries
+        // and rejects the `+` sign `from_str_radix` would accept.
+        if s.len() != 6 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
             return None;
         }
 
@@ -40,6 +45,105 @@ impl Color {
         Some(Color::Rgb(r, g, b))
     }
 
+    /// Looks up one of the 16 named colors, or `reset`, case-insensitively.
+    ///
+    /// Bright variants take a `bright-` or `bright_` prefix, e.g.
+    /// `"bright-red"`. Returns `None` for any other name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "reset" {
+            return Some(Color::Reset);
+        }
+        let (base, offset) = match name
+            .strip_prefix("bright-")
+            .or_else(|| name.strip_prefix("bright_"))
+        {
+            Some(base) => (base, 8),
+            None => (name.as_str(), 0),
+        };
+        let index = COLOR_NAMES.iter().position(|n| *n == base)?;
+        Some(ANSI_16[index + offset])
+    }
+
+    /// Resolves the color to an RGB triple.
+    ///
+    /// Named and indexed colors use the standard xterm palette. Returns `None`
+    /// for [`Color::Reset`], which has no fixed value.
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        let rgb = match self {
+            Color::Reset => return None,
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+            Color::BrightBlack => (127, 127, 127),
+            Color::BrightRed => (255, 0, 0),
+            Color::BrightGreen => (0, 255, 0),
+            Color::BrightYellow => (255, 255, 0),
+            Color::BrightBlue => (92, 92, 255),
+            Color::BrightMagenta => (255, 0, 255),
+            Color::BrightCyan => (0, 255, 255),
+            Color::BrightWhite => (255, 255, 255),
+            Color::Indexed(i) => match i {
+                0..=15 => return ANSI_16[*i as usize].to_rgb(),
+                16..=231 => {
+                    let i = i - 16;
+                    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                    (level(i / 36), level((i / 6) % 6), level(i % 6))
+                }
+                232..=255 => {
+                    let v = 8 + (i - 232) * 10;
+                    (v, v, v)
+                }
+            },
+            Color::Rgb(r, g, b) => (*r, *g, *b),
+        };
+        Some(rgb)
+    }
+
+    /// Formats the color as a `#rrggbb` hex string.
+    ///
+    /// Returns `None` for [`Color::Reset`].
+    pub fn to_hex(&self) -> Option<String> {
+        let (r, g, b) = self.to_rgb()?;
+        Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    /// Maps an [`Rgb`](Color::Rgb) color to the nearest entry of the xterm
+    /// 256-color palette: the 6×6×6 cube (16-231) or the grayscale ramp
+    /// (232-255).
+    ///
+    /// Other colors are returned unchanged.
+    pub fn to_256(&self) -> Color {
+        let Color::Rgb(r, g, b) = *self else {
+            return *self;
+        };
+
+        let distance = |(r2, g2, b2): (u8, u8, u8)| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(r, r2) + d(g, g2) + d(b, b2)
+        };
+
+        // Nearest cube level per channel; the levels are 0, 95, 135, ..., 255.
+        let level = |v: u8| match v {
+            0..48 => 0,
+            48..115 => 1,
+            _ => (v - 35) / 40,
+        };
+        let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+        // Nearest step of the ramp 8, 18, ..., 238.
+        let avg = (r as u16 + g as u16 + b as u16) / 3;
+        let gray = 232 + (avg.saturating_sub(3) / 10).min(23) as u8;
+
+        let pick = |i: u8| (distance(Color::Indexed(i).to_rgb().unwrap()), i);
+        Color::Indexed(pick(cube).min(pick(gray)).1)
+    }
+
     pub fn to_ansi_fg(&self) -> String {
         match self {
             Color::Reset => "39".to_string(),
@@ -64,6 +168,23 @@ impl Color {
         }
     }
 
+    /// Returns the SGR 58 parameters that set this color as the underline
+    /// color, e.g. `58;2;255;0;0`.
+    ///
+    /// SGR 58 only takes indexed and RGB colors, so named colors use their
+    /// index in the 16-color palette.
+    pub fn to_ansi_underline(&self) -> String {
+        match self {
+            Color::Reset => "59".to_string(),
+            Color::Indexed(i) => format!("58;5;{}", i),
+            Color::Rgb(r, g, b) => format!("58;2;{};{};{}", r, g, b),
+            named => {
+                let index = ANSI_16.iter().position(|c| c == named).unwrap_or(0);
+                format!("58;5;{}", index)
+            }
+        }
+    }
+
     pub fn to_ansi_bg(&self) -> String {
         match self {
             Color::Reset => "49".to_string(),
@@ -89,8 +210,87 @@ impl Color {
     }
 }
 
+/// The 16 named colors, in ANSI index order.
+const ANSI_16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// The names of the first 8 of [`ANSI_16`], for [`Color::from_name`].
+const COLOR_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// The error returned when a string isn't a valid [`Color`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError {
+    input: String,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown color {:?}: expected a color name, \"#rrggbb\", \"indexed:N\" or \"rgb(r, g, b)\"",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses a color as written in a config file.
+    ///
+    /// Accepts a name for [`Color::from_name`], `#rrggbb` hex,
+    /// `indexed:N` with `N` in `0..=255`, and `rgb(r, g, b)`. Surrounding
+    /// whitespace and letter case are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().to_ascii_lowercase();
+        let color = if input.starts_with('#') {
+            Color::from_hex(&input)
+        } else if let Some(index) = input.strip_prefix("indexed:") {
+            index.trim().parse().ok().map(Color::Indexed)
+        } else if let Some(args) = input
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let channels: Vec<u8> = args
+                .split(',')
+                .map(|c| c.trim().parse())
+                .collect::<Result<_, _>>()
+                .unwrap_or_default();
+            match channels[..] {
+                [r, g, b] => Some(Color::Rgb(r, g, b)),
+                _ => None,
+            }
+        } else {
+            Color::from_name(&input)
+        };
+        color.ok_or_else(|| ParseColorError {
+            input: s.to_string(),
+        })
+    }
+}
+
 /// A bitflag representing text modifiers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub struct Modifier(u16);
 
 impl Modifier {
@@ -100,17 +300,27 @@ impl Modifier {
     pub const REVERSED: Self = Self(0b0000_1000);
     pub const DIM: Self = Self(0b0001_0000);
 
-    pub fn empty() -> Self {
+    pub const fn empty() -> Self {
         Self(0)
     }
 
-    pub fn contains(self, other: Self) -> bool {
+    pub const fn contains(self, other: Self) -> bool {
         (self.0 & other.0) == other.0
     }
 
-    pub fn insert(&mut self, other: Self) {
+    pub const fn insert(&mut self, other: Self) {
         self.0 |= other.0;
     }
+
+    /// Clears the flags set in `other`.
+    pub const fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Flips the flags set in `other`.
+    pub const fn toggle(&mut self, other: Self) {
+        self.0 ^= other.0;
+    }
 }
 
 impl std::ops::BitOr for Modifier {
@@ -121,38 +331,121 @@ impl std::ops::BitOr for Modifier {
 }
 
 /// Represents the visual style of a cell.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub struct Style {
     pub foreground: Option<Color>,
     pub background: Option<Color>,
+    /// The color of the underline, if different from the text (SGR 58).
+    pub underline_color: Option<Color>,
     pub modifiers: Modifier,
 }
 
 impl Style {
     /// Creates a new, default style (no colors, no modifiers).
-    pub fn new() -> Self {
-        Self::default()
+    ///
+    /// Like the other constructors and builders, it is a `const fn`, so
+    /// styles can be declared as constants:
+    ///
+    /// ```
+    /// use phosphor::{Color, Modifier, Style};
+    ///
+    /// const TITLE: Style = Style::new().fg(Color::Cyan).modifier(Modifier::BOLD);
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            foreground: None,
+            background: None,
+            underline_color: None,
+            modifiers: Modifier::empty(),
+        }
+    }
+
+    /// A style with only the foreground color set.
+    pub const fn fg_color(color: Color) -> Self {
+        Self::new().fg(color)
+    }
+
+    /// A style with only [`Modifier::BOLD`] set.
+    pub const fn bold() -> Self {
+        Self::new().modifier(Modifier::BOLD)
+    }
+
+    /// Bold red, for error messages.
+    pub const fn error() -> Self {
+        Self::fg_color(Color::Red).modifier(Modifier::BOLD)
+    }
+
+    /// Green, for confirmations and healthy states.
+    pub const fn success() -> Self {
+        Self::fg_color(Color::Green)
+    }
+
+    /// Yellow, for warnings.
+    pub const fn warning() -> Self {
+        Self::fg_color(Color::Yellow)
     }
 
     /// Sets the foreground color.
-    pub fn fg(mut self, color: Color) -> Self {
+    pub const fn fg(mut self, color: Color) -> Self {
         self.foreground = Some(color);
         self
     }
 
     /// Sets the background color.
-    pub fn bg(mut self, color: Color) -> Self {
+    pub const fn bg(mut self, color: Color) -> Self {
         self.background = Some(color);
         self
     }
 
+    /// Sets the underline color, independent of the text color.
+    ///
+    /// Only visible together with [`Modifier::UNDERLINE`]. Terminals without
+    /// support for SGR 58 ignore it.
+    pub const fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
     /// Adds a modifier.
-    pub fn modifier(mut self, modifier: Modifier) -> Self {
+    pub const fn modifier(mut self, modifier: Modifier) -> Self {
         self.modifiers.insert(modifier);
         self
     }
 
-    // TODO: Implement a `patch` or `inherit` method to combine styles.
+    /// Removes a modifier.
+    ///
+    /// Note that [`patch`](Self::patch) only ever adds modifiers, so remove
+    /// them from the patched result.
+    pub const fn remove_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers.remove(modifier);
+        self
+    }
+
+    /// Layers `other` on top of this style.
+    ///
+    /// Colors set in `other` replace this style's; unset ones are kept.
+    /// Modifiers from both are combined.
+    pub fn patch(mut self, other: Style) -> Self {
+        if other.foreground.is_some() {
+            self.foreground = other.foreground;
+        }
+        if other.background.is_some() {
+            self.background = other.background;
+        }
+        if other.underline_color.is_some() {
+            self.underline_color = other.underline_color;
+        }
+        self.modifiers.insert(other.modifiers);
+        self
+    }
+
+    /// Returns the style with both colors passed through [`Color::to_256`].
+    pub fn to_256(mut self) -> Self {
+        self.foreground = self.foreground.map(|c| c.to_256());
+        self.background = self.background.map(|c| c.to_256());
+        self.underline_color = self.underline_color.map(|c| c.to_256());
+        self
+    }
 
     pub fn to_ansi(&self) -> String {
         let mut codes = vec!["0".to_string()];
@@ -163,6 +456,9 @@ impl Style {
         if let Some(bg) = self.background {
             codes.push(bg.to_ansi_bg());
         }
+        if let Some(color) = self.underline_color {
+            codes.push(color.to_ansi_underline());
+        }
         if self.modifiers.contains(Modifier::BOLD) {
             codes.push("1".to_string());
         }
@@ -187,6 +483,72 @@ impl Style {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_style_underline_color() {
+        let underlined = Style::new().modifier(Modifier::UNDERLINE);
+        assert_eq!(underlined.to_ansi(), "\x1b[0;4m");
+
+        let colored = underlined.underline_color(Color::Rgb(255, 0, 0));
+        assert_eq!(colored.to_ansi(), "\x1b[0;58;2;255;0;0;4m");
+        assert_eq!(
+            Style::new().underline_color(Color::BrightRed).to_ansi(),
+            "\x1b[0;58;5;9m"
+        );
+    }
+
+    #[test]
+    fn test_color_to_256_cube() {
+        assert_eq!(Color::Rgb(255, 0, 0).to_256(), Color::Indexed(196));
+        assert_eq!(Color::Rgb(0, 0, 0).to_256(), Color::Indexed(16));
+        assert_eq!(Color::Rgb(90, 140, 250).to_256(), Color::Indexed(69));
+    }
+
+    #[test]
+    fn test_color_to_256_grayscale() {
+        assert_eq!(Color::Rgb(128, 130, 129).to_256(), Color::Indexed(244));
+        assert_eq!(Color::Blue.to_256(), Color::Blue);
+        assert_eq!(Color::Indexed(7).to_256(), Color::Indexed(7));
+    }
+
+    #[test]
+    fn test_style_presets() {
+        const TITLE: Style = Style::fg_color(Color::Cyan).modifier(Modifier::ITALIC);
+        assert_eq!(
+            TITLE,
+            Style::new().fg(Color::Cyan).modifier(Modifier::ITALIC)
+        );
+        assert_eq!(Style::new(), Style::default());
+
+        assert_eq!(Style::bold().modifiers, Modifier::BOLD);
+        assert_eq!(Style::bold().foreground, None);
+
+        let error = Style::error();
+        assert_eq!(error.foreground, Some(Color::Red));
+        assert!(error.modifiers.contains(Modifier::BOLD));
+        assert_eq!(Style::success().foreground, Some(Color::Green));
+        assert_eq!(Style::warning().foreground, Some(Color::Yellow));
+        assert_eq!(Style::warning().modifiers, Modifier::empty());
+    }
+
+    #[test]
+    fn test_modifier_remove_and_toggle() {
+        let mut modifiers = Modifier::BOLD | Modifier::ITALIC | Modifier::DIM;
+        modifiers.remove(Modifier::ITALIC);
+        assert_eq!(modifiers, Modifier::BOLD | Modifier::DIM);
+        // Removing a flag that isn't set changes nothing.
+        modifiers.remove(Modifier::UNDERLINE);
+        assert_eq!(modifiers, Modifier::BOLD | Modifier::DIM);
+
+        let original = modifiers;
+        modifiers.toggle(Modifier::BOLD | Modifier::REVERSED);
+        assert_eq!(modifiers, Modifier::DIM | Modifier::REVERSED);
+        modifiers.toggle(Modifier::BOLD | Modifier::REVERSED);
+        assert_eq!(modifiers, original);
+
+        let style = Style::error().remove_modifier(Modifier::BOLD);
+        assert_eq!(style, Style::fg_color(Color::Red));
+    }
+
     #[test]
     fn test_style_builder() {
         let style = Style::new()
@@ -210,6 +572,53 @@ mod tests {
         assert_eq!(Color::from_hex("invalid"), None);
     }
 
+    #[test]
+    fn test_color_from_name() {
+        assert_eq!(Color::from_name("red"), Some(Color::Red));
+        assert_eq!(Color::from_name("Bright-Red"), Some(Color::BrightRed));
+        assert_eq!(Color::from_name("bright_white"), Some(Color::BrightWhite));
+        assert_eq!(Color::from_name("RESET"), Some(Color::Reset));
+        assert_eq!(Color::from_name("brightred"), None);
+        assert_eq!(Color::from_name("orange"), None);
+    }
+
+    #[test]
+    fn test_color_from_str() {
+        assert_eq!("cyan".parse(), Ok(Color::Cyan));
+        assert_eq!(" bright-magenta ".parse(), Ok(Color::BrightMagenta));
+        assert_eq!("#FF5733".parse(), Ok(Color::Rgb(255, 87, 51)));
+        assert_eq!("indexed:244".parse(), Ok(Color::Indexed(244)));
+        assert_eq!("RGB(1, 2,3)".parse(), Ok(Color::Rgb(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_color_from_str_rejects_invalid() {
+        for input in [
+            "orange",
+            "#12345",
+            "#aééb",
+            "#+1+2+3",
+            "indexed:256",
+            "rgb(1,2)",
+            "rgb(1,2,300)",
+            "",
+        ] {
+            assert!(input.parse::<Color>().is_err(), "{:?}", input);
+        }
+        let err = "orange".parse::<Color>().unwrap_err();
+        assert!(err.to_string().starts_with("unknown color \"orange\""));
+    }
+
+    #[test]
+    fn test_color_to_hex() {
+        assert_eq!(Color::Rgb(18, 52, 86).to_hex(), Some("#123456".to_string()));
+        assert_eq!(Color::BrightWhite.to_hex(), Some("#ffffff".to_string()));
+        assert_eq!(Color::Indexed(1).to_hex(), Color::Red.to_hex());
+        assert_eq!(Color::Indexed(196).to_hex(), Some("#ff0000".to_string()));
+        assert_eq!(Color::Indexed(232).to_hex(), Some("#080808".to_string()));
+        assert_eq!(Color::Reset.to_hex(), None);
+    }
+
     #[test]
     fn test_color_to_ansi() {
         assert_eq!(Color::Red.to_ansi_fg(), "31");
@@ -233,4 +642,19 @@ mod tests {
         // Assuming: Reset; FG; BG; Modifiers
         assert_eq!(style.to_ansi(), "\x1b[0;31;44;1m");
     }
+
+    #[test]
+    fn test_style_patch() {
+        let base = Style::new().fg(Color::Red).bg(Color::Black);
+
+        let bold = base.patch(Style::new().modifier(Modifier::BOLD));
+        assert_eq!(bold.foreground, Some(Color::Red));
+        assert_eq!(bold.background, Some(Color::Black));
+        assert!(bold.modifiers.contains(Modifier::BOLD));
+
+        let blue = bold.patch(Style::new().bg(Color::Blue).modifier(Modifier::ITALIC));
+        assert_eq!(blue.foreground, Some(Color::Red));
+        assert_eq!(blue.background, Some(Color::Blue));
+        assert!(blue.modifiers.contains(Modifier::BOLD | Modifier::ITALIC));
+    }
 }
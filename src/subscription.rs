@@ -0,0 +1,194 @@
+//! The `subscription` module lets applications declare event sources other
+//! than terminal input.
+//!
+//! Instead of spawning ad-hoc threads, an application returns a list of
+//! [`Subscription`]s from [`Application::subscriptions`](crate::Application::subscriptions).
+//! The runtime watches them and feeds the resulting actions into
+//! [`Application::update`](crate::Application::update), just like input events.
+//!
+//! # Example
+//! ```no_run
+//! use std::time::Duration;
+//! use phosphor::Subscription;
+//!
+//! enum Action {
+//!     Tick,
+//! }
+//!
+//! let every_second: Subscription<'_, Action> =
+//!     Subscription::interval(Duration::from_secs(1), |_| Action::Tick);
+//! ```
+
+use std::os::fd::RawFd;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+use crate::terminal::Terminal;
+
+/// A declarative source of actions managed by the runtime.
+pub struct Subscription<'a, A> {
+    kind: Kind<'a, A>,
+}
+
+enum Kind<'a, A> {
+    Interval(Duration, Box<dyn Fn(Instant) -> A + 'a>),
+    Fd(RawFd, Box<dyn Fn() -> A + 'a>),
+    Channel(&'a Receiver<A>),
+}
+
+impl<'a, A> Subscription<'a, A> {
+    /// Produces an action every `every`, starting one period after the
+    /// subscription first appears.
+    ///
+    /// The closure receives the instant at which the timer fired. Timers are
+    /// identified by their position among the interval subscriptions, so keep
+    /// the order stable across frames to avoid resetting them.
+    pub fn interval<F>(every: Duration, f: F) -> Self
+    where
+        F: Fn(Instant) -> A + 'a,
+    {
+        Self {
+            kind: Kind::Interval(every, Box::new(f)),
+        }
+    }
+
+    /// Produces an action whenever the given file descriptor is readable.
+    ///
+    /// The action fires on every loop iteration while data is pending, so the
+    /// application should drain the descriptor when handling it.
+    pub fn fd<F>(fd: RawFd, f: F) -> Self
+    where
+        F: Fn() -> A + 'a,
+    {
+        Self {
+            kind: Kind::Fd(fd, Box::new(f)),
+        }
+    }
+
+    /// Forwards every action sent on the channel.
+    ///
+    /// This is the bridge for work done on background threads.
+    pub fn channel(receiver: &'a Receiver<A>) -> Self {
+        Self {
+            kind: Kind::Channel(receiver),
+        }
+    }
+}
+
+/// How long the loop waits for input when only non-timer sources are active.
+const SOURCE_POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Runtime bookkeeping for the subscriptions of an application.
+#[derive(Default)]
+pub(crate) struct Subscriptions {
+    /// `(period, next deadline)` for each interval, by position.
+    timers: Vec<(Duration, Instant)>,
+    /// Whether any fd or channel source was active on the last poll.
+    has_sources: bool,
+}
+
+impl Subscriptions {
+    /// Collects the actions produced by `subscriptions` at time `now`.
+    pub(crate) fn poll<A>(
+        &mut self,
+        subscriptions: Vec<Subscription<'_, A>>,
+        now: Instant,
+        terminal: &Terminal,
+    ) -> Vec<A> {
+        let mut actions = Vec::new();
+        let mut timer = 0;
+        self.has_sources = false;
+
+        for subscription in subscriptions {
+            match subscription.kind {
+                Kind::Interval(every, f) => {
+                    match self.timers.get_mut(timer) {
+                        Some((period, deadline)) if *period == every => {
+                            if now >= *deadline {
+                                actions.push(f(now));
+                                *deadline += every;
+                                // Don't burst to catch up after a stall.
+                                if *deadline <= now {
+                                    *deadline = now + every;
+                                }
+                            }
+                        }
+                        Some(slot) => *slot = (every, now + every),
+                        None => self.timers.push((every, now + every)),
+                    }
+                    timer += 1;
+                }
+                Kind::Fd(fd, f) => {
+                    self.has_sources = true;
+                    if let Ok(true) = terminal.poll_fd(fd, Duration::ZERO) {
+                        actions.push(f());
+                    }
+                }
+                Kind::Channel(receiver) => {
+                    self.has_sources = true;
+                    actions.extend(receiver.try_iter());
+                }
+            }
+        }
+
+        self.timers.truncate(timer);
+        actions
+    }
+
+    /// Returns how long the loop may wait for input before a subscription
+    /// needs attention, or `None` if there are no active subscriptions.
+    pub(crate) fn timeout(&self, now: Instant) -> Option<Duration> {
+        let next_timer = self
+            .timers
+            .iter()
+            .map(|(_, deadline)| deadline.saturating_duration_since(now))
+            .min();
+
+        match (next_timer, self.has_sources) {
+            (Some(t), true) => Some(t.min(SOURCE_POLL_INTERVAL)),
+            (Some(t), false) => Some(t),
+            (None, true) => Some(SOURCE_POLL_INTERVAL),
+            (None, false) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::mocks::MockSystem;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_interval_fires_after_period() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let mut subs = Subscriptions::default();
+        let t0 = Instant::now();
+        let every = Duration::from_millis(100);
+
+        let fired = subs.poll(vec![Subscription::interval(every, |_| ())], t0, &terminal);
+        assert!(fired.is_empty());
+        assert_eq!(subs.timeout(t0), Some(every));
+
+        let fired = subs.poll(
+            vec![Subscription::interval(every, |_| ())],
+            t0 + every,
+            &terminal,
+        );
+        assert_eq!(fired.len(), 1);
+    }
+
+    #[test]
+    fn test_channel_forwards_all_pending() {
+        let terminal = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let mut subs = Subscriptions::default();
+        let (tx, rx) = mpsc::channel();
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+
+        let actions = subs.poll(vec![Subscription::channel(&rx)], Instant::now(), &terminal);
+
+        assert_eq!(actions, vec![1, 2]);
+        assert_eq!(subs.timeout(Instant::now()), Some(SOURCE_POLL_INTERVAL));
+    }
+}
@@ -12,16 +12,30 @@
 //! * [`LibcSystem`]: The production implementation using `libc` FFI.
 //! * [`Terminal`]: The high-level wrapper used by the application.
 
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::ffi::c_void;
 use std::io;
 use std::os::fd::RawFd;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::input::{Event, Parser};
+use crate::style::Color;
+
+/// How long [`Terminal::get_cursor_position`] waits for the terminal's reply.
+const CURSOR_QUERY_TIMEOUT: Duration = Duration::from_millis(200);
 
 /// Abstraction over system calls relative to the terminal.
 ///
 /// This trait acts as a "seam" for testing, allowing the [`Terminal`] struct to
 /// interact with a mock OS during unit tests instead of making real syscalls.
-pub trait System {
+///
+/// Implementations must be `Send + Sync` so the terminal can still be
+/// restored from a panic hook (see
+/// [`RunOptions::restore_on_panic`](crate::RunOptions::restore_on_panic)).
+pub trait System: Send + Sync {
     /// Opens a file descriptor to the current TTY (usually `/dev/tty`).
     ///
     /// # Errors
@@ -72,6 +86,28 @@ pub trait System {
     /// Returns `Ok(true)` if data is ready, `Ok(false)` if the timeout expired,
     /// or `Err` if the system call failed.
     fn poll(&self, fd: RawFd, timeout: Duration) -> io::Result<bool>;
+
+    /// Starts recording window size changes, for [`take_resized`](Self::take_resized).
+    ///
+    /// Returns `false` if the backend can't report them, in which case the
+    /// caller has to poll [`get_window_size`](Self::get_window_size) instead.
+    /// The default implementation returns `false`.
+    fn watch_resize(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the window was resized since the last call, once
+    /// [`watch_resize`](Self::watch_resize) succeeded.
+    fn take_resized(&self) -> bool {
+        false
+    }
+}
+
+/// Set by the `SIGWINCH` handler installed by [`LibcSystem::watch_resize`].
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigwinch(_signal: libc::c_int) {
+    RESIZED.store(true, Ordering::Relaxed);
 }
 
 /// The production implementation of [`System`] using `libc` calls.
@@ -205,19 +241,233 @@ impl System for LibcSystem {
             Ok(ret > 0)
         }
     }
+
+    /// Installs a `SIGWINCH` handler that flags the resize.
+    ///
+    /// The signal also interrupts a pending [`poll`](System::poll), so a
+    /// resize is noticed without waiting for its timeout. `SA_RESTART` keeps
+    /// it from failing blocking reads and writes on the tty with `EINTR`.
+    fn watch_resize(&self) -> bool {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_sigwinch as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut()) == 0
+        }
+    }
+
+    fn take_resized(&self) -> bool {
+        RESIZED.swap(false, Ordering::Relaxed)
+    }
 }
 
 use std::fmt;
 
+/// An in-memory [`System`] for running applications without a TTY.
+///
+/// It reports a fixed window size, serves input pushed with
+/// [`push_input`](Self::push_input) and records everything written. Clones
+/// share the same state, so keep a clone to inspect the output after handing
+/// the backend to a [`Terminal`].
+#[derive(Clone, Default)]
+pub struct TestBackend {
+    size: Arc<Mutex<(u16, u16)>>,
+    input: Arc<Mutex<VecDeque<u8>>>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl TestBackend {
+    /// Creates a backend with a window of the given size.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: Arc::new(Mutex::new((width, height))),
+            ..Self::default()
+        }
+    }
+
+    /// Changes the reported window size.
+    pub fn resize(&self, width: u16, height: u16) {
+        *self.size.lock().unwrap() = (width, height);
+    }
+
+    /// Queues bytes to be read as terminal input.
+    pub fn push_input(&self, bytes: &[u8]) {
+        self.input.lock().unwrap().extend(bytes);
+    }
+
+    /// Returns a copy of everything written so far.
+    pub fn output(&self) -> Vec<u8> {
+        self.output.lock().unwrap().clone()
+    }
+}
+
+impl System for TestBackend {
+    fn open_tty(&self) -> io::Result<RawFd> {
+        Ok(-1)
+    }
+
+    fn close_tty(&self, _fd: RawFd) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn enable_raw(&self, _fd: RawFd) -> io::Result<libc::termios> {
+        Ok(unsafe { std::mem::zeroed() })
+    }
+
+    fn disable_raw(&self, _fd: RawFd, _original: &libc::termios) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn get_window_size(&self, _fd: RawFd) -> io::Result<(u16, u16)> {
+        Ok(*self.size.lock().unwrap())
+    }
+
+    /// Never blocks: returns `Ok(0)` when no input is queued.
+    fn read(&self, _fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+        let mut input = self.input.lock().unwrap();
+        let len = buf.len().min(input.len());
+        for (dst, src) in buf.iter_mut().zip(input.drain(..len)) {
+            *dst = src;
+        }
+        Ok(len)
+    }
+
+    fn write(&self, _fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+        self.output.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn poll(&self, _fd: RawFd, _timeout: Duration) -> io::Result<bool> {
+        Ok(!self.input.lock().unwrap().is_empty())
+    }
+}
+
 /// A high-level wrapper around the terminal state and I/O.
 ///
 /// This struct manages the lifecycle of **Raw Mode** using the RAII pattern.
 /// When a `Terminal` is created, it takes control of the TTY. When it is dropped,
 /// it automatically restores the original terminal configuration.
 pub struct Terminal {
-    system: Box<dyn System>,
+    system: Arc<dyn System>,
     fd: RawFd,
     original_termios: Option<libc::termios>,
+    background_set: Cell<bool>,
+    cursor_hidden: Cell<bool>,
+    mouse_captured: Cell<bool>,
+    /// The motion tracking mode (`1002` or `1003`) enabled, if any.
+    mouse_motion: Cell<Option<u16>>,
+    focus_reporting: Cell<bool>,
+    alternate_screen: Cell<bool>,
+}
+
+/// Chooses which terminal modes a [`Terminal`] switches on when acquired.
+///
+/// Raw mode is always enabled. Everything else defaults to on, matching
+/// [`Terminal::new`]; turn modes off when embedding the renderer in a loop
+/// that doesn't want them. Dropping the terminal only undoes the modes that
+/// are still enabled.
+///
+/// ```no_run
+/// use phosphor::terminal::Terminal;
+///
+/// let terminal = Terminal::builder()
+///     .mouse_capture(false)
+///     .alternate_buffer(false)
+///     .build()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TerminalBuilder {
+    hide_cursor: bool,
+    mouse_capture: bool,
+    focus_events: bool,
+    alternate_buffer: bool,
+}
+
+impl Default for TerminalBuilder {
+    fn default() -> Self {
+        Self {
+            hide_cursor: true,
+            mouse_capture: true,
+            focus_events: true,
+            alternate_buffer: true,
+        }
+    }
+}
+
+impl TerminalBuilder {
+    /// Sets whether the cursor is hidden on acquire (default: true).
+    pub fn hide_cursor(mut self, hide: bool) -> Self {
+        self.hide_cursor = hide;
+        self
+    }
+
+    /// Sets whether mouse reporting is enabled on acquire (default: true).
+    pub fn mouse_capture(mut self, capture: bool) -> Self {
+        self.mouse_capture = capture;
+        self
+    }
+
+    /// Sets whether focus changes are reported as
+    /// [`Event::FocusGained`]/[`Event::FocusLost`] (default: true).
+    pub fn focus_events(mut self, focus_events: bool) -> Self {
+        self.focus_events = focus_events;
+        self
+    }
+
+    /// Sets whether to switch to the alternate screen on acquire
+    /// (default: true).
+    pub fn alternate_buffer(mut self, alternate: bool) -> Self {
+        self.alternate_buffer = alternate;
+        self
+    }
+
+    /// Acquires the TTY through the default [`LibcSystem`].
+    ///
+    /// # Errors
+    /// Returns an error if `/dev/tty` cannot be opened or if Raw Mode cannot be enabled.
+    pub fn build(self) -> io::Result<Terminal> {
+        self.build_with_system(Box::new(LibcSystem))
+    }
+
+    /// Acquires the TTY through a specific system backend.
+    ///
+    /// # Errors
+    /// Returns an error if the TTY cannot be opened or if Raw Mode cannot be enabled.
+    pub fn build_with_system(self, system: Box<dyn System>) -> io::Result<Terminal> {
+        let fd = system.open_tty()?;
+
+        let mut term = Terminal {
+            system: system.into(),
+            fd,
+            original_termios: None,
+            background_set: Cell::new(false),
+            cursor_hidden: Cell::new(false),
+            mouse_captured: Cell::new(false),
+            mouse_motion: Cell::new(None),
+            focus_reporting: Cell::new(false),
+            alternate_screen: Cell::new(false),
+        };
+
+        let termios = term.system.enable_raw(fd)?;
+        term.original_termios = Some(termios);
+
+        if self.hide_cursor {
+            term.hide_cursor()?;
+        }
+        if self.mouse_capture {
+            term.enable_mouse_capture()?;
+        }
+        if self.focus_events {
+            term.enable_focus_reporting()?;
+        }
+        if self.alternate_buffer {
+            term.enter_alternate_buffer()?;
+        }
+
+        Ok(term)
+    }
 }
 
 impl fmt::Debug for Terminal {
@@ -243,22 +493,12 @@ impl Terminal {
     ///
     /// This is primarily used for dependency injection in tests.
     pub fn new_with_system(system: Box<dyn System>) -> io::Result<Self> {
-        let fd = system.open_tty()?;
-
-        let mut term = Self {
-            system,
-            fd,
-            original_termios: None,
-        };
-
-        let termios = term.system.enable_raw(fd)?;
-        term.original_termios = Some(termios);
-
-        term.hide_cursor()?;
-        term.enable_mouse_capture()?;
-        term.enter_alternate_buffer()?;
+        Self::builder().build_with_system(system)
+    }
 
-        Ok(term)
+    /// Returns a [`TerminalBuilder`] for choosing which modes to enable.
+    pub fn builder() -> TerminalBuilder {
+        TerminalBuilder::default()
     }
 
     /// Returns the current size of the terminal as `(cols, rows)`.
@@ -266,6 +506,20 @@ impl Terminal {
         self.system.get_window_size(self.fd)
     }
 
+    /// Asks the backend to report window resizes (see [`take_resized`](Self::take_resized)).
+    ///
+    /// Returns `false` if it can't, in which case [`size`](Self::size) has to
+    /// be polled to notice them.
+    pub fn watch_resize(&self) -> bool {
+        self.system.watch_resize()
+    }
+
+    /// Returns whether the window was resized since the last call, once
+    /// [`watch_resize`](Self::watch_resize) returned `true`.
+    pub fn take_resized(&self) -> bool {
+        self.system.take_resized()
+    }
+
     /// Reads raw bytes from the terminal into the provided buffer.
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.system.read(self.fd, buf)
@@ -276,6 +530,29 @@ impl Terminal {
         self.system.write(self.fd, buf)
     }
 
+    /// Writes all of `buf` to the terminal, continuing after short writes
+    /// and retrying writes interrupted by a signal.
+    ///
+    /// # Errors
+    /// Returns [`io::ErrorKind::WriteZero`] if the terminal stops accepting
+    /// bytes, or the first error other than [`io::ErrorKind::Interrupted`].
+    pub fn write_all(&self, mut buf: &[u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "terminal accepted no bytes",
+                    ));
+                }
+                Ok(n) => buf = &buf[n..],
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
     /// Checks if data is available to read in the given timeout.
     ///
     /// This is used to differentiate between ambiguous keys (like `Esc` vs `Alt+...`).
@@ -285,49 +562,230 @@ impl Terminal {
         self.system.poll(self.fd, timeout)
     }
 
+    /// Checks if data is available to read on an arbitrary file descriptor.
+    ///
+    /// This uses the same [`System`] backend as the terminal itself, so it can
+    /// be mocked in tests.
+    pub fn poll_fd(&self, fd: RawFd, timeout: Duration) -> io::Result<bool> {
+        self.system.poll(fd, timeout)
+    }
+
     /// Shows the terminal cursor.
     pub fn show_cursor(&self) -> io::Result<()> {
-        self.write(b"\x1b[?25h")?;
+        self.write_all(b"\x1b[?25h")?;
+        self.cursor_hidden.set(false);
         Ok(())
     }
 
     /// Hides the terminal cursor.
     pub fn hide_cursor(&self) -> io::Result<()> {
-        self.write(b"\x1b[?25l")?;
+        self.write_all(b"\x1b[?25l")?;
+        self.cursor_hidden.set(true);
         Ok(())
     }
 
+    /// Moves the terminal cursor to the given 0-based column and row.
+    pub fn move_cursor(&self, x: u16, y: u16) -> io::Result<()> {
+        self.write_all(format!("\x1b[{};{}H", y + 1, x + 1).as_bytes())?;
+        Ok(())
+    }
+
+    /// Asks the terminal where the cursor is (`\x1b[6n`) and returns its
+    /// 0-based `(column, row)`.
+    ///
+    /// Any other input that arrives before the reply is discarded, so call it
+    /// before starting to read events.
+    ///
+    /// # Errors
+    /// Returns [`io::ErrorKind::TimedOut`] if the terminal does not answer
+    /// within 200ms.
+    pub fn get_cursor_position(&self) -> io::Result<(u16, u16)> {
+        self.write_all(b"\x1b[6n")?;
+
+        let deadline = Instant::now() + CURSOR_QUERY_TIMEOUT;
+        let mut parser = Parser::new().cursor_reports(true);
+        let mut buf = [0u8; 32];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || !self.poll(remaining)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "terminal did not report the cursor position",
+                ));
+            }
+            let n = self.read(&mut buf)?;
+            for event in parser.parse(&buf[..n]) {
+                if let Event::CursorPosition(x, y) = event {
+                    return Ok((x, y));
+                }
+            }
+        }
+    }
+
     /// Switches the terminal to the alternate screen buffer.
     pub fn enter_alternate_buffer(&self) -> io::Result<()> {
-        self.write(b"\x1b[?1049h")?;
+        self.write_all(b"\x1b[?1049h")?;
+        self.alternate_screen.set(true);
         Ok(())
     }
 
     /// Switches the terminal back to the main screen buffer.
     pub fn exit_alternate_buffer(&self) -> io::Result<()> {
-        self.write(b"\x1b[?1049l")?;
+        self.write_all(b"\x1b[?1049l")?;
+        self.alternate_screen.set(false);
         Ok(())
     }
 
+    /// Sets the terminal's default background color using OSC 11.
+    ///
+    /// Passing [`Color::Reset`] restores the terminal's own default. A custom
+    /// background is automatically restored when the `Terminal` is dropped.
+    pub fn set_background(&self, color: Color) -> io::Result<()> {
+        match color.to_hex() {
+            Some(hex) => {
+                self.write_all(format!("\x1b]11;{}\x07", hex).as_bytes())?;
+                self.background_set.set(true);
+            }
+            None => self.reset_background()?,
+        }
+        Ok(())
+    }
+
+    /// Sets the terminal window and icon title (OSC 0).
+    ///
+    /// Control characters are dropped, since an ESC or BEL in the title would
+    /// end the sequence early and leak the rest to the screen.
+    pub fn set_title(&self, title: &str) -> io::Result<()> {
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        self.write_all(format!("\x1b]0;{}\x07", title).as_bytes())?;
+        Ok(())
+    }
+
+    /// Restores the terminal's default background color (OSC 111).
+    pub fn reset_background(&self) -> io::Result<()> {
+        self.write_all(b"\x1b]111\x07")?;
+        self.background_set.set(false);
+        Ok(())
+    }
+
+    /// Enables mouse click reporting (`?1000`).
     pub fn enable_mouse_capture(&self) -> io::Result<()> {
-        self.write(b"\x1b[?1000h")?;
+        self.write_all(b"\x1b[?1000h")?;
+        self.mouse_captured.set(true);
         Ok(())
     }
 
+    /// Disables mouse click reporting.
     pub fn disable_mouse_capture(&self) -> io::Result<()> {
-        self.write(b"\x1b[?1000l")?;
+        self.write_all(b"\x1b[?1000l")?;
+        self.mouse_captured.set(false);
+        Ok(())
+    }
+
+    /// Also reports the pointer moving while a button is held (`?1002`), as
+    /// [`MouseKind::Drag`](crate::MouseKind::Drag).
+    pub fn enable_mouse_drag(&self) -> io::Result<()> {
+        self.enable_mouse_motion(1002)
+    }
+
+    /// Reports every pointer movement (`?1003`): drags as
+    /// [`MouseKind::Drag`](crate::MouseKind::Drag), and movement with no
+    /// button held as [`MouseKind::Moved`](crate::MouseKind::Moved).
+    pub fn enable_mouse_all(&self) -> io::Result<()> {
+        self.enable_mouse_motion(1003)
+    }
+
+    /// Stops the motion reports enabled by [`enable_mouse_drag`](Self::enable_mouse_drag)
+    /// or [`enable_mouse_all`](Self::enable_mouse_all).
+    pub fn disable_mouse_motion(&self) -> io::Result<()> {
+        if let Some(mode) = self.mouse_motion.get() {
+            self.write_all(format!("\x1b[?{}l", mode).as_bytes())?;
+            self.mouse_motion.set(None);
+        }
         Ok(())
     }
+
+    fn enable_mouse_motion(&self, mode: u16) -> io::Result<()> {
+        self.disable_mouse_motion()?;
+        self.write_all(format!("\x1b[?{}h", mode).as_bytes())?;
+        self.mouse_motion.set(Some(mode));
+        Ok(())
+    }
+
+    /// Enables focus reporting (`?1004`): the terminal sends `ESC [ I` and
+    /// `ESC [ O` when its window gains or loses focus.
+    pub fn enable_focus_reporting(&self) -> io::Result<()> {
+        self.write_all(b"\x1b[?1004h")?;
+        self.focus_reporting.set(true);
+        Ok(())
+    }
+
+    /// Disables focus reporting.
+    pub fn disable_focus_reporting(&self) -> io::Result<()> {
+        self.write_all(b"\x1b[?1004l")?;
+        self.focus_reporting.set(false);
+        Ok(())
+    }
+
+    /// Returns a function that puts the terminal back in a usable state:
+    /// mouse capture and focus reporting off, main screen, cursor shown, raw
+    /// mode off.
+    ///
+    /// Only the mouse, focus and screen modes enabled when it is created are undone;
+    /// the cursor is always shown, since it may be toggled every frame.
+    ///
+    /// Unlike dropping the `Terminal`, it can be called from a panic hook,
+    /// before the panic message is printed. The terminal's `Drop` still runs
+    /// afterwards; restoring twice is harmless.
+    pub(crate) fn restorer(&self) -> impl Fn() + Send + Sync + 'static {
+        let system = Arc::clone(&self.system);
+        let fd = self.fd;
+        let original = self.original_termios;
+        let mut sequence = String::new();
+        if let Some(mode) = self.mouse_motion.get() {
+            sequence.push_str(&format!("\x1b[?{}l", mode));
+        }
+        if self.mouse_captured.get() {
+            sequence.push_str("\x1b[?1000l");
+        }
+        if self.focus_reporting.get() {
+            sequence.push_str("\x1b[?1004l");
+        }
+        if self.alternate_screen.get() {
+            sequence.push_str("\x1b[?1049l");
+        }
+        sequence.push_str("\x1b[?25h");
+        move || {
+            let _ = system.write(fd, sequence.as_bytes());
+            if let Some(termios) = original {
+                let _ = system.disable_raw(fd, &termios);
+            }
+        }
+    }
 }
 
 impl Drop for Terminal {
-    /// Automatically restores the terminal configuration.
+    /// Automatically restores the terminal configuration, undoing only the
+    /// modes that are still enabled.
     ///
     /// If restoration fails, the error is logged to `debug.log`.
     fn drop(&mut self) {
-        let _ = self.disable_mouse_capture();
-        let _ = self.exit_alternate_buffer();
-        let _ = self.show_cursor();
+        if self.background_set.get() {
+            let _ = self.reset_background();
+        }
+        let _ = self.disable_mouse_motion();
+        if self.mouse_captured.get() {
+            let _ = self.disable_mouse_capture();
+        }
+        if self.focus_reporting.get() {
+            let _ = self.disable_focus_reporting();
+        }
+        if self.alternate_screen.get() {
+            let _ = self.exit_alternate_buffer();
+        }
+        if self.cursor_hidden.get() {
+            let _ = self.show_cursor();
+        }
 
         if let Some(termios) = self.original_termios
             && let Err(e) = self.system.disable_raw(self.fd, &termios)
@@ -342,7 +800,6 @@ impl Drop for Terminal {
 #[cfg(test)]
 pub(crate) mod mocks {
     use super::*;
-    use std::sync::{Arc, Mutex};
 
     #[derive(Default)]
     pub struct MockSystem {
@@ -351,6 +808,15 @@ pub(crate) mod mocks {
         pub fail_open: bool,
         pub fail_enable_raw: bool,
         pub max_read_size: Option<usize>,
+        /// Accept at most this many bytes per `write`, to simulate short writes.
+        pub max_write_size: Option<usize>,
+        /// Fail this many upcoming writes with `Interrupted`.
+        pub interrupted_writes: Arc<Mutex<usize>>,
+        /// The reported window size; `None` means 80x24.
+        pub window_size: Arc<Mutex<Option<(u16, u16)>>>,
+        /// Stands in for the `SIGWINCH` flag when set, see
+        /// [`with_resize_signal`](Self::with_resize_signal).
+        pub resized: Option<Arc<AtomicBool>>,
     }
 
     impl MockSystem {
@@ -363,6 +829,18 @@ pub(crate) mod mocks {
             self
         }
 
+        pub fn with_max_write(mut self, size: usize) -> Self {
+            self.max_write_size = Some(size);
+            self
+        }
+
+        /// Reports resizes through [`System::take_resized`]: set `resized`
+        /// to simulate the signal.
+        pub fn with_resize_signal(mut self) -> Self {
+            self.resized = Some(Arc::new(AtomicBool::new(false)));
+            self
+        }
+
         pub fn push_input(&self, data: &[u8]) {
             self.input_buffer.lock().unwrap().extend_from_slice(data);
         }
@@ -378,7 +856,7 @@ pub(crate) mod mocks {
         fn open_tty(&self) -> io::Result<RawFd> {
             self.push_log("open_tty");
             if self.fail_open {
-                return Err(io::Error::new(io::ErrorKind::Other, "Mock Open Failed"));
+                return Err(io::Error::other("Mock Open Failed"));
             }
             Ok(100)
         }
@@ -391,10 +869,7 @@ pub(crate) mod mocks {
         fn enable_raw(&self, fd: RawFd) -> io::Result<libc::termios> {
             self.push_log(&format!("enable_raw({})", fd));
             if self.fail_enable_raw {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "Mock Enable Raw Failed",
-                ));
+                return Err(io::Error::other("Mock Enable Raw Failed"));
             }
             // Return empty termios
             Ok(unsafe { std::mem::zeroed() })
@@ -407,7 +882,7 @@ pub(crate) mod mocks {
 
         fn get_window_size(&self, fd: RawFd) -> io::Result<(u16, u16)> {
             self.push_log(&format!("get_window_size({})", fd));
-            Ok((80, 24))
+            Ok(self.window_size.lock().unwrap().unwrap_or((80, 24)))
         }
 
         fn read(&self, fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
@@ -430,15 +905,33 @@ pub(crate) mod mocks {
         }
 
         fn write(&self, fd: RawFd, buf: &[u8]) -> io::Result<usize> {
-            let content = String::from_utf8_lossy(buf);
+            let mut interrupted = self.interrupted_writes.lock().unwrap();
+            if *interrupted > 0 {
+                *interrupted -= 1;
+                return Err(io::Error::from(io::ErrorKind::Interrupted));
+            }
+            let len = self
+                .max_write_size
+                .map_or(buf.len(), |max| buf.len().min(max));
+            let content = String::from_utf8_lossy(&buf[..len]);
             self.push_log(&format!("write({}, \"{}\")", fd, content));
-            Ok(buf.len())
+            Ok(len)
         }
 
         fn poll(&self, _fd: RawFd, _timeout: Duration) -> io::Result<bool> {
             let input = self.input_buffer.lock().unwrap();
             Ok(!input.is_empty())
         }
+
+        fn watch_resize(&self) -> bool {
+            self.resized.is_some()
+        }
+
+        fn take_resized(&self) -> bool {
+            self.resized
+                .as_ref()
+                .is_some_and(|flag| flag.swap(false, Ordering::Relaxed))
+        }
     }
 }
 
@@ -481,16 +974,220 @@ mod tests {
         assert_eq!(log[1], "enable_raw(100)");
         assert_eq!(log[2], "write(100, \"\x1b[?25l\")");
         assert_eq!(log[3], "write(100, \"\x1b[?1000h\")");
-        assert_eq!(log[4], "write(100, \"\x1b[?1049h\")");
-        assert_eq!(log[5], "get_window_size(100)");
-        assert_eq!(log[6], "write(100, \"foo\")");
-        assert_eq!(log[7], "read(100)");
-        assert_eq!(log[8], "write(100, \"\x1b[?1000l\")");
-        assert_eq!(log[9], "write(100, \"\x1b[?1049l\")");
-        assert_eq!(log[10], "write(100, \"\x1b[?25h\")");
-        assert_eq!(log[11], "disable_raw(100)");
-        assert_eq!(log[12], "close_tty");
-        assert_eq!(log.len(), 13);
+        assert_eq!(log[4], "write(100, \"\x1b[?1004h\")");
+        assert_eq!(log[5], "write(100, \"\x1b[?1049h\")");
+        assert_eq!(log[6], "get_window_size(100)");
+        assert_eq!(log[7], "write(100, \"foo\")");
+        assert_eq!(log[8], "read(100)");
+        assert_eq!(log[9], "write(100, \"\x1b[?1000l\")");
+        assert_eq!(log[10], "write(100, \"\x1b[?1004l\")");
+        assert_eq!(log[11], "write(100, \"\x1b[?1049l\")");
+        assert_eq!(log[12], "write(100, \"\x1b[?25h\")");
+        assert_eq!(log[13], "disable_raw(100)");
+        assert_eq!(log[14], "close_tty");
+        assert_eq!(log.len(), 15);
+    }
+
+    #[test]
+    fn test_builder_without_mouse_capture() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+
+        {
+            let _term = Terminal::builder()
+                .mouse_capture(false)
+                .build_with_system(Box::new(mock))
+                .unwrap();
+        }
+
+        let log = log_ref.lock().unwrap();
+        assert!(!log.iter().any(|s| s.contains("?1000")));
+        assert!(log.contains(&"write(100, \"\x1b[?1049h\")".to_string()));
+        assert!(log.contains(&"write(100, \"\x1b[?1049l\")".to_string()));
+    }
+
+    #[test]
+    fn test_builder_restores_only_enabled_modes() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+
+        {
+            let _term = Terminal::builder()
+                .hide_cursor(false)
+                .mouse_capture(false)
+                .focus_events(false)
+                .alternate_buffer(false)
+                .build_with_system(Box::new(mock))
+                .unwrap();
+        }
+
+        let log = log_ref.lock().unwrap();
+        assert_eq!(
+            *log,
+            [
+                "open_tty",
+                "enable_raw(100)",
+                "disable_raw(100)",
+                "close_tty"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mouse_motion_modes_restored() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+
+        {
+            let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+            term.enable_mouse_drag().unwrap();
+            // Switching modes turns the previous one off first.
+            term.enable_mouse_all().unwrap();
+        }
+
+        let log = log_ref.lock().unwrap();
+        let writes: Vec<&str> = log
+            .iter()
+            .filter(|l| ["?1000", "?1002", "?1003"].iter().any(|m| l.contains(m)))
+            .map(|l| l.as_str())
+            .collect();
+        assert_eq!(
+            writes[writes.len() - 5..],
+            [
+                "write(100, \"\x1b[?1002h\")",
+                "write(100, \"\x1b[?1002l\")",
+                "write(100, \"\x1b[?1003h\")",
+                "write(100, \"\x1b[?1003l\")",
+                "write(100, \"\x1b[?1000l\")",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_cursor_position_parses_reply() {
+        let mock = MockSystem::new().with_max_read(3);
+        mock.push_input(b"a\x1b[5;10R");
+        let log_ref = mock.log.clone();
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        assert_eq!(term.get_cursor_position().unwrap(), (9, 4));
+        assert!(
+            log_ref
+                .lock()
+                .unwrap()
+                .contains(&"write(100, \"\x1b[6n\")".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_cursor_position_times_out_without_reply() {
+        let term = Terminal::new_with_system(Box::new(MockSystem::new())).unwrap();
+        let err = term.get_cursor_position().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn test_size_follows_window_size() {
+        let mock = MockSystem::new();
+        let window_size = mock.window_size.clone();
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+        assert_eq!(term.size().unwrap(), (80, 24));
+
+        *window_size.lock().unwrap() = Some((120, 40));
+        assert_eq!(term.size().unwrap(), (120, 40));
+    }
+
+    #[test]
+    fn test_write_all_resumes_short_and_interrupted_writes() {
+        let mock = MockSystem::new().with_max_write(2);
+        let log = mock.log.clone();
+        let interrupted = mock.interrupted_writes.clone();
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+        log.lock().unwrap().clear();
+        *interrupted.lock().unwrap() = 1;
+
+        term.write_all(b"hello").unwrap();
+
+        let writes: Vec<String> = log
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.starts_with("write("))
+            .cloned()
+            .collect();
+        assert_eq!(
+            writes,
+            [
+                "write(100, \"he\")",
+                "write(100, \"ll\")",
+                "write(100, \"o\")"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_writes_retry_after_signal() {
+        let mock = MockSystem::new();
+        let log = mock.log.clone();
+        let interrupted = mock.interrupted_writes.clone();
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+        *interrupted.lock().unwrap() = 2;
+
+        term.hide_cursor().unwrap();
+        term.set_title("t").unwrap();
+
+        let log = log.lock().unwrap();
+        assert!(log.contains(&"write(100, \"\x1b[?25l\")".to_string()));
+        assert!(log.contains(&"write(100, \"\x1b]0;t\x07\")".to_string()));
+    }
+
+    #[test]
+    fn test_test_backend_records_output() {
+        let backend = TestBackend::new(40, 10);
+        let term = Terminal::new_with_system(Box::new(backend.clone())).unwrap();
+        backend.push_input(b"hi");
+
+        term.write(b"foo").unwrap();
+        let mut buf = [0u8; 8];
+
+        assert_eq!(term.size().unwrap(), (40, 10));
+        assert_eq!(term.read(&mut buf).unwrap(), 2);
+        assert_eq!(term.read(&mut buf).unwrap(), 0);
+        assert!(backend.output().ends_with(b"foo"));
+    }
+
+    #[test]
+    fn test_set_title_strips_control_chars() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+        let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+
+        term.set_title("a\x1b]0;b\x07c\n").unwrap();
+
+        let log = log_ref.lock().unwrap();
+        assert_eq!(log.last().unwrap(), "write(100, \"\x1b]0;a]0;bc\x07\")");
+    }
+
+    #[test]
+    fn test_background_restored_on_drop() {
+        let mock = MockSystem::new();
+        let log_ref = mock.log.clone();
+
+        {
+            let term = Terminal::new_with_system(Box::new(mock)).unwrap();
+            term.set_background(Color::Rgb(0, 0, 255)).unwrap();
+        }
+
+        let log = log_ref.lock().unwrap();
+        let set = log
+            .iter()
+            .position(|s| s == "write(100, \"\x1b]11;#0000ff\x07\")")
+            .expect("OSC 11 not written");
+        let reset = log
+            .iter()
+            .position(|s| s == "write(100, \"\x1b]111\x07\")")
+            .expect("OSC 111 not written");
+        assert!(set < reset);
     }
 
     #[test]
@@ -0,0 +1,261 @@
+//! Helpers for testing applications without a terminal.
+//!
+//! A [`TestHarness`] drives an [`Application`] directly: events go through
+//! [`on_event`](Application::on_event) and [`update`](Application::update),
+//! and the app is drawn into an in-memory [`Buffer`] after each one.
+//!
+//! ```
+//! use phosphor::testing::TestHarness;
+//! use phosphor::{Application, Command, Frame};
+//!
+//! struct Hello;
+//!
+//! impl Application for Hello {
+//!     type Action = ();
+//!     fn update(&mut self, _: ()) -> Command {
+//!         Command::None
+//!     }
+//!     fn draw(&self, frame: &mut Frame) {
+//!         frame.write_str(0, 0, "hello");
+//!     }
+//! }
+//!
+//! let harness = TestHarness::new(Hello, 8, 1);
+//! assert_eq!(harness.to_string(), "hello   ");
+//! ```
+
+use std::fmt;
+
+use crate::{Application, Buffer, Cell, Command, Event, Frame, KeyCode, KeyEvent, Rect};
+
+/// Runs an [`Application`] against a fixed-size [`Buffer`], one event at a
+/// time.
+///
+/// The harness applies the commands returned by the app as far as they make
+/// sense without a terminal: [`Command::Quit`] is recorded (see
+/// [`has_quit`](Self::has_quit)), [`Command::Inject`] is delivered right away
+/// and [`Command::Batch`] is applied in order. Commands that talk to the
+/// terminal or the clock, such as titles and ticks, are ignored.
+pub struct TestHarness<App: Application> {
+    app: App,
+    buffer: Buffer,
+    ascii_only: bool,
+    quit: bool,
+}
+
+impl<App: Application> TestHarness<App> {
+    /// Wraps `app` with a `width` x `height` screen, runs
+    /// [`init`](Application::init) and draws the first frame.
+    pub fn new(app: App, width: u16, height: u16) -> Self {
+        let mut harness = Self {
+            app,
+            buffer: Buffer::new(width, height),
+            ascii_only: false,
+            quit: false,
+        };
+        let command = harness.app.init();
+        harness.apply(command);
+        harness.draw();
+        harness
+    }
+
+    /// Draws with ASCII-only glyphs, as with
+    /// [`RunOptions::ascii_only`](crate::RunOptions::ascii_only).
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self.draw();
+        self
+    }
+
+    /// Delivers `event` to the app, applies the resulting command and redraws.
+    ///
+    /// Events sent after the app quit are ignored.
+    pub fn send(&mut self, event: Event) -> &mut Self {
+        self.dispatch(event);
+        self.draw();
+        self
+    }
+
+    /// Sends a key press without modifiers.
+    pub fn send_key(&mut self, code: KeyCode) -> &mut Self {
+        self.send(Event::Key(KeyEvent::new(code)))
+    }
+
+    /// Sends each character of `text` as a key press.
+    pub fn type_str(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.dispatch(Event::Key(KeyEvent::new(KeyCode::Char(c))));
+        }
+        self.draw();
+        self
+    }
+
+    /// Redraws the app into a blank buffer.
+    pub fn draw(&mut self) {
+        self.buffer = Buffer::new(self.buffer.width, self.buffer.height);
+        let area = Rect::new(0, 0, self.buffer.width, self.buffer.height);
+        let mut frame = Frame::new(&mut self.buffer, area).ascii_only(self.ascii_only);
+        self.app.draw(&mut frame);
+    }
+
+    /// Changes the screen size and redraws.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.buffer = Buffer::new(width, height);
+        self.draw();
+    }
+
+    /// Returns the application, to inspect its state.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Returns the last drawn frame.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the cell at the given coordinates of the last frame.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds.
+    pub fn cell(&self, x: u16, y: u16) -> &Cell {
+        self.buffer.get(x, y)
+    }
+
+    /// Returns the text of row `y` of the last frame.
+    pub fn row(&self, y: u16) -> String {
+        (0..self.buffer.width)
+            .map(|x| self.buffer.get(x, y))
+            .filter(|cell| !cell.is_continuation())
+            .map(|cell| cell.symbol)
+            .collect()
+    }
+
+    /// Returns whether the app returned [`Command::Quit`].
+    pub fn has_quit(&self) -> bool {
+        self.quit
+    }
+
+    fn dispatch(&mut self, event: Event) {
+        if self.quit {
+            return;
+        }
+        if let Some(action) = self.app.on_event(event) {
+            let command = self.app.update(action);
+            self.apply(command);
+        }
+    }
+
+    fn apply(&mut self, command: Command) {
+        match command {
+            Command::Quit => self.quit = true,
+            Command::Inject(event) => self.dispatch(event),
+            Command::Batch(commands) => {
+                for command in commands {
+                    if self.quit {
+                        break;
+                    }
+                    self.apply(command);
+                }
+            }
+            Command::None
+            | Command::NoRedraw
+            | Command::Redraw
+            | Command::SetBackground(_)
+            | Command::SetTitle(_)
+            | Command::Tick(_) => {
+                // Nothing to apply without a terminal or a clock.
+            }
+        }
+    }
+}
+
+impl<App: Application> fmt::Display for TestHarness<App> {
+    /// Writes the last frame's text, one line per row.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.buffer.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::{Block, Borders, Text};
+    use crate::{Constraint, Layout};
+
+    /// The `counter` example.
+    struct Counter {
+        value: i32,
+    }
+
+    enum Action {
+        Increment,
+        Decrement,
+        Quit,
+    }
+
+    impl Application for Counter {
+        type Action = Action;
+
+        fn on_event(&self, event: Event) -> Option<Action> {
+            match event {
+                Event::Key(key) => match key.code {
+                    KeyCode::Char('+') => Some(Action::Increment),
+                    KeyCode::Char('-') => Some(Action::Decrement),
+                    KeyCode::Char('q') => Some(Action::Quit),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+
+        fn update(&mut self, msg: Action) -> Command {
+            match msg {
+                Action::Increment => self.value += 1,
+                Action::Decrement => self.value -= 1,
+                Action::Quit => return Command::Quit,
+            }
+            Command::None
+        }
+
+        fn draw(&self, frame: &mut Frame) {
+            let [header, body] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill]).areas(frame.area());
+            frame.render_widget(Text::new("Counter Example"), header);
+            let inner = frame.render_block(Block::new().borders(Borders::ALL), body);
+            frame.render_widget(Text::new(format!("Count: {}", self.value)), inner);
+        }
+    }
+
+    #[test]
+    fn test_harness_drives_counter() {
+        let mut harness = TestHarness::new(Counter { value: 0 }, 20, 4).ascii_only(true);
+        assert_eq!(harness.row(2), "|Count: 0          |");
+
+        harness.type_str("+++-").send_key(KeyCode::Char('+'));
+
+        assert_eq!(harness.app().value, 3);
+        assert_eq!(
+            harness.to_string(),
+            [
+                "Counter Example     ",
+                "+------------------+",
+                "|Count: 3          |",
+                "+------------------+",
+            ]
+            .join("\n")
+        );
+        assert_eq!(harness.cell(1, 2).symbol, 'C');
+    }
+
+    #[test]
+    fn test_harness_records_quit() {
+        let mut harness = TestHarness::new(Counter { value: 0 }, 20, 4);
+        harness.send_key(KeyCode::Char('q'));
+        assert!(harness.has_quit());
+
+        // Nothing is delivered after quitting.
+        harness.send_key(KeyCode::Char('+'));
+        assert_eq!(harness.app().value, 0);
+    }
+}
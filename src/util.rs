@@ -0,0 +1,97 @@
+//! The `util` module collects small helpers for common application patterns.
+
+use std::time::{Duration, Instant};
+
+/// Tracks an action that only goes through if it is requested twice within a
+/// time window, e.g. "press q again to quit".
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, Instant};
+/// use phosphor::util::Confirm;
+///
+/// let mut confirm = Confirm::new(Duration::from_secs(2));
+/// let now = Instant::now();
+///
+/// // First press: nothing is pending yet, so arm it.
+/// assert_eq!(confirm.fire_if_confirmed(now), None);
+/// confirm.arm("quit", now);
+///
+/// // Second press within the window goes through.
+/// assert_eq!(confirm.fire_if_confirmed(now + Duration::from_secs(1)), Some("quit"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Confirm<A> {
+    window: Duration,
+    pending: Option<(A, Instant)>,
+}
+
+impl<A> Confirm<A> {
+    /// Creates a helper that waits up to `window` for the confirmation.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: None,
+        }
+    }
+
+    /// Stores `action` as pending until `now + window`, replacing any previous one.
+    pub fn arm(&mut self, action: A, now: Instant) {
+        self.pending = Some((action, now + self.window));
+    }
+
+    /// Returns `true` if an action is pending and its window has not expired.
+    ///
+    /// Useful for showing a "press again to confirm" hint.
+    pub fn is_armed(&self, now: Instant) -> bool {
+        matches!(self.pending, Some((_, deadline)) if now <= deadline)
+    }
+
+    /// Drops the pending action, if any.
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+
+    /// Takes the pending action if it is still within its window.
+    ///
+    /// An expired action is discarded, so the next request must arm again.
+    pub fn fire_if_confirmed(&mut self, now: Instant) -> Option<A> {
+        match self.pending.take() {
+            Some((action, deadline)) if now <= deadline => Some(action),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_within_window() {
+        let mut confirm = Confirm::new(Duration::from_millis(500));
+        let t0 = Instant::now();
+
+        confirm.arm("quit", t0);
+        assert!(confirm.is_armed(t0 + Duration::from_millis(200)));
+
+        assert_eq!(
+            confirm.fire_if_confirmed(t0 + Duration::from_millis(200)),
+            Some("quit")
+        );
+        // Firing consumes the pending action.
+        assert_eq!(confirm.fire_if_confirmed(t0), None);
+    }
+
+    #[test]
+    fn test_confirm_after_window_expires() {
+        let mut confirm = Confirm::new(Duration::from_millis(500));
+        let t0 = Instant::now();
+
+        confirm.arm("quit", t0);
+        let late = t0 + Duration::from_millis(600);
+
+        assert!(!confirm.is_armed(late));
+        assert_eq!(confirm.fire_if_confirmed(late), None);
+    }
+}
@@ -1,6 +1,6 @@
 //! A container widget with optional borders and title.
 
-use crate::{Frame, Rect, Style, widgets::Widget};
+use crate::{Alignment, Frame, Rect, Style, buffer::char_width, widgets::Widget};
 
 const P_BORDER_H: char = '\u{2500}';
 const P_BORDER_V: char = '\u{2502}';
@@ -23,6 +23,10 @@ const D_BORDER_TR: char = '\u{2557}';
 const D_BORDER_BL: char = '\u{255A}';
 const D_BORDER_BR: char = '\u{255D}';
 
+const A_BORDER_H: char = '-';
+const A_BORDER_V: char = '|';
+const A_BORDER_CORNER: char = '+';
+
 /// The style of the borders.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BorderType {
@@ -33,6 +37,8 @@ pub enum BorderType {
     Rounded,
     /// Double lines (╔, ═, ╗, etc.).
     Double,
+    /// Plain ASCII (`+`, `-`, `|`) for terminals without UTF-8 support.
+    Ascii,
 }
 
 impl BorderType {
@@ -64,6 +70,24 @@ impl BorderType {
                 D_BORDER_BL,
                 D_BORDER_BR,
             ),
+            BorderType::Ascii => (
+                A_BORDER_H,
+                A_BORDER_V,
+                A_BORDER_CORNER,
+                A_BORDER_CORNER,
+                A_BORDER_CORNER,
+                A_BORDER_CORNER,
+            ),
+        }
+    }
+
+    /// Returns the border type to draw with in the given frame, falling back to
+    /// [`BorderType::Ascii`] when the frame is restricted to ASCII.
+    pub fn resolve(self, frame: &Frame) -> Self {
+        if frame.is_ascii_only() {
+            BorderType::Ascii
+        } else {
+            self
         }
     }
 }
@@ -95,12 +119,16 @@ impl std::ops::BitOr for Borders {
 /// A container widget that can display a border and a title.
 pub struct Block {
     title: Option<String>,
+    titles: Vec<(String, Alignment)>,
+    title_bottom: Option<String>,
+    title_alignment: Alignment,
     borders: Borders,
     border_type: BorderType,
     style: Style,
     title_style: Style,
     padding_x: u16,
     padding_y: u16,
+    fill: bool,
 }
 
 impl Default for Block {
@@ -113,12 +141,16 @@ impl Block {
     pub fn new() -> Self {
         Self {
             title: None,
+            titles: Vec::new(),
+            title_bottom: None,
+            title_alignment: Alignment::Left,
             borders: Borders::NONE,
             border_type: BorderType::Rounded,
             style: Style::default(),
             title_style: Style::default(),
             padding_x: 0,
             padding_y: 0,
+            fill: false,
         }
     }
 
@@ -127,6 +159,28 @@ impl Block {
         self
     }
 
+    /// Adds another title to the top edge with its own alignment.
+    ///
+    /// Titles are laid out without overlapping. When they don't all fit, the
+    /// one added last is truncated first, or dropped entirely; the title set
+    /// with [`title`](Self::title) always wins.
+    pub fn title_aligned<S: Into<String>>(mut self, title: S, alignment: Alignment) -> Self {
+        self.titles.push((title.into(), alignment));
+        self
+    }
+
+    /// Sets a title drawn on the bottom edge.
+    pub fn title_bottom<S: Into<String>>(mut self, title: S) -> Self {
+        self.title_bottom = Some(title.into());
+        self
+    }
+
+    /// Sets where the titles sit along their edge (default: left).
+    pub fn title_alignment(mut self, alignment: Alignment) -> Self {
+        self.title_alignment = alignment;
+        self
+    }
+
     pub fn borders(mut self, borders: Borders) -> Self {
         self.borders = borders;
         self
@@ -168,6 +222,21 @@ impl Block {
         self
     }
 
+    /// Fills the whole area with spaces in the block's style before drawing
+    /// the borders, so nothing drawn earlier shows through (default: false).
+    pub fn fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Wraps `inner` so it renders inside this block.
+    ///
+    /// The returned widget draws the block, then `inner` in the block's
+    /// [`inner`](Self::inner) area.
+    pub fn wrap<W: Widget>(self, inner: W) -> Wrapped<W> {
+        Wrapped { block: self, inner }
+    }
+
     /// Returns the inner area of the block, excluding the borders and padding.
     ///
     /// This is useful for rendering other widgets inside the block.
@@ -203,13 +272,24 @@ impl Block {
 
 impl Widget for Block {
     fn render(self, area: Rect, frame: &mut Frame) {
-        let (h, v, tl, tr, bl, br) = self.border_type.get_chars();
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let (h, v, tl, tr, bl, br) = self.border_type.resolve(frame).get_chars();
         frame.with_style(self.style, |f| {
             f.render_area(area, |f| {
                 let width = f.width();
                 let height = f.height();
                 let mut buf = [0u8; 4];
 
+                if self.fill {
+                    let blank = " ".repeat(width as usize);
+                    for y in 0..height {
+                        f.write_str(0, y, &blank);
+                    }
+                }
+
                 // 1. Draw Sides (Edge-to-Edge)
                 if self.borders.contains(Borders::TOP) {
                     let s = h.encode_utf8(&mut buf);
@@ -237,6 +317,8 @@ impl Widget for Block {
                 }
 
                 // 2. Draw Corners (Overwrite intersections)
+                // With a single row or column, opposite edges collapse onto the
+                // same cells, so the straight line drawn above is kept instead.
                 let corners = [
                     (0, 0, Borders::TOP | Borders::LEFT, tl),
                     (width - 1, 0, Borders::TOP | Borders::RIGHT, tr),
@@ -245,28 +327,152 @@ impl Widget for Block {
                 ];
 
                 for (x, y, req, sym) in corners {
-                    if self.borders.contains(req) {
+                    if width >= 2 && height >= 2 && self.borders.contains(req) {
                         f.write_str(x, y, sym.encode_utf8(&mut buf));
                     }
                 }
 
-                // 3. Draw Title
-                if let Some(t) = self.title {
-                    let style = if self.title_style == Style::default() {
-                        self.style
-                    } else {
-                        self.title_style
-                    };
-
-                    f.with_style(style, |f| {
-                        f.write_str(2, 0, &format!(" {} ", t));
-                    });
+                // 3. Draw Titles
+                let style = if self.title_style == Style::default() {
+                    self.style
+                } else {
+                    self.title_style
+                };
+                let alignment = self.title_alignment;
+                let top = self.title.map(|t| (t, alignment));
+                let top: Vec<_> = top.into_iter().chain(self.titles).collect();
+                let bottom: Vec<_> = self
+                    .title_bottom
+                    .map(|t| (t, alignment))
+                    .into_iter()
+                    .collect();
+
+                // With a single row, the bottom edge is the top edge.
+                let mut rows = vec![(top, 0)];
+                if height >= 2 {
+                    rows.push((bottom, height - 1));
+                }
+
+                for (titles, y) in rows {
+                    for (x, title) in layout_titles(titles, width) {
+                        f.with_style(style, |f| {
+                            f.write_str(x, y, &title);
+                        });
+                    }
                 }
             });
         });
     }
 }
 
+/// A widget drawn inside a [`Block`], created with [`Block::wrap`].
+pub struct Wrapped<W> {
+    block: Block,
+    inner: W,
+}
+
+impl<W: Widget> Widget for Wrapped<W> {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        let inner = frame.render_block(self.block, area);
+        self.inner.render(inner, frame);
+    }
+}
+
+/// Positions padded titles along an edge `width` cells wide, in priority order.
+/// Titles are measured in columns, so wide chars count twice.
+///
+/// A left-aligned title starts at column 2 and a right-aligned one ends at
+/// `width - 2`; neither may cover a corner. Titles sharing an alignment are
+/// stacked with one border cell between them, and the lowest-priority title
+/// is shortened one character at a time until nothing collides.
+fn layout_titles(titles: Vec<(String, Alignment)>, width: u16) -> Vec<(u16, String)> {
+    let available = width.saturating_sub(3) as i32;
+    let mut titles: Vec<(Vec<char>, Alignment)> = titles
+        .into_iter()
+        .map(|(t, a)| {
+            let mut used = 0;
+            let padded = format!(" {} ", t)
+                .chars()
+                .take_while(|&c| {
+                    used += char_width(c) as i32;
+                    used <= available
+                })
+                .collect::<Vec<_>>();
+            (padded, a)
+        })
+        .filter(|(t, _)| !t.is_empty())
+        .collect();
+
+    loop {
+        if let Some(xs) = place_titles(&titles, width) {
+            return xs
+                .into_iter()
+                .zip(titles)
+                .map(|(x, (t, _))| (x, t.into_iter().collect()))
+                .collect();
+        }
+        // Only the leading pad left means the title is gone.
+        if let Some((t, _)) = titles.last_mut() {
+            t.pop();
+            if t.len() <= 1 {
+                titles.pop();
+            }
+        }
+    }
+}
+
+/// Returns the number of terminal columns `title` occupies.
+fn columns(title: &[char]) -> i32 {
+    title.iter().map(|&c| char_width(c) as i32).sum()
+}
+
+/// Returns the starting column of each title, or `None` if any collide.
+fn place_titles(titles: &[(Vec<char>, Alignment)], width: u16) -> Option<Vec<u16>> {
+    let width = width as i32;
+    let mut xs = vec![0; titles.len()];
+    let of = |alignment| {
+        titles
+            .iter()
+            .enumerate()
+            .filter(move |(_, (_, a))| *a == alignment)
+            .map(|(i, (t, _))| (i, columns(t)))
+    };
+
+    let mut spans = Vec::new();
+    let mut x = 2;
+    for (i, len) in of(Alignment::Left) {
+        xs[i] = x;
+        x += len + 1;
+    }
+    if x > 2 {
+        spans.push((2, x - 1));
+    }
+
+    let total: i32 = of(Alignment::Center).map(|(_, len)| len + 1).sum();
+    if total > 0 {
+        let start = (width - total + 1) / 2;
+        let mut x = start;
+        for (i, len) in of(Alignment::Center) {
+            xs[i] = x;
+            x += len + 1;
+        }
+        spans.push((start, x - 1));
+    }
+
+    let mut x = width - 2;
+    for (i, len) in of(Alignment::Right) {
+        xs[i] = x - len;
+        x -= len + 1;
+    }
+    if x < width - 2 {
+        spans.push((x + 1, width - 2));
+    }
+
+    let inside = spans.iter().all(|&(start, end)| start >= 1 && end < width);
+    let apart = spans.windows(2).all(|w| w[0].1 < w[1].0);
+    (inside && apart).then(|| xs.into_iter().map(|x| x as u16).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +511,22 @@ mod tests {
         assert_eq!(buffer.get(2, 0).symbol, D_BORDER_H);
     }
 
+    #[test]
+    fn test_block_render_ascii_only() {
+        let mut buffer = Buffer::new(5, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 3)).ascii_only(true);
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double);
+
+        block.render(Rect::new(0, 0, 5, 3), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, '+');
+        assert_eq!(buffer.get(4, 2).symbol, '+');
+        assert_eq!(buffer.get(2, 0).symbol, '-');
+        assert_eq!(buffer.get(0, 1).symbol, '|');
+    }
+
     #[test]
     fn test_block_render_title() {
         let mut buffer = Buffer::new(10, 3);
@@ -337,6 +559,241 @@ mod tests {
         assert_eq!(buffer.get(3, 0).style.foreground, Some(Color::Red));
     }
 
+    #[test]
+    fn test_block_render_1x1() {
+        let mut buffer = Buffer::new(3, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 3));
+        let block = Block::new().borders(Borders::ALL).title("Title");
+
+        block.render(Rect::new(1, 1, 1, 1), &mut frame);
+
+        assert_eq!(buffer.get(1, 1).symbol, R_BORDER_V);
+        // Nothing leaks outside the area
+        for (x, y) in [(0, 1), (2, 1), (1, 0), (1, 2)] {
+            assert_eq!(buffer.get(x, y).symbol, ' ');
+        }
+    }
+
+    #[test]
+    fn test_block_render_2x2() {
+        let mut buffer = Buffer::new(4, 4);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 4, 4));
+        let block = Block::new().borders(Borders::ALL).title("Title");
+
+        block.render(Rect::new(1, 1, 2, 2), &mut frame);
+
+        assert_eq!(buffer.get(1, 1).symbol, R_BORDER_TL);
+        assert_eq!(buffer.get(2, 1).symbol, R_BORDER_TR);
+        assert_eq!(buffer.get(1, 2).symbol, R_BORDER_BL);
+        assert_eq!(buffer.get(2, 2).symbol, R_BORDER_BR);
+        assert_eq!(buffer.get(3, 1).symbol, ' ');
+    }
+
+    #[test]
+    fn test_block_render_1x3() {
+        let mut buffer = Buffer::new(3, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 3));
+        let block = Block::new().borders(Borders::ALL);
+
+        block.render(Rect::new(1, 0, 1, 3), &mut frame);
+
+        for y in 0..3 {
+            assert_eq!(buffer.get(1, y).symbol, R_BORDER_V);
+            assert_eq!(buffer.get(2, y).symbol, ' ');
+        }
+    }
+
+    #[test]
+    fn test_block_render_title_truncated() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new().borders(Borders::ALL).title("Long Title");
+
+        block.render(Rect::new(0, 0, 8, 3), &mut frame);
+
+        // " Long Title " is cut to fit columns 2..=6, keeping the corner
+        assert_eq!(buffer.get(6, 0).symbol, 'g');
+        assert_eq!(buffer.get(7, 0).symbol, R_BORDER_TR);
+        assert_eq!(buffer.get(8, 0).symbol, ' ');
+    }
+
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.width).map(|x| buffer.get(x, y).symbol).collect()
+    }
+
+    #[test]
+    fn test_block_title_centered() {
+        let mut buffer = Buffer::new(20, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("Hi")
+            .title_alignment(Alignment::Center);
+
+        block.render(Rect::new(0, 0, 20, 3), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "+------- Hi -------+");
+    }
+
+    #[test]
+    fn test_block_title_right_keeps_corner() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title_alignment(Alignment::Right);
+
+        block.title("Hi").render(Rect::new(0, 0, 10, 3), &mut frame);
+        assert_eq!(row(&buffer, 0), "+--- Hi -+");
+
+        // A title too long for the edge is cut, still clear of both corners.
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title_alignment(Alignment::Right)
+            .title("Long Title");
+        block.render(Rect::new(0, 0, 10, 3), &mut frame);
+        assert_eq!(row(&buffer, 0), "+ Long T-+");
+    }
+
+    #[test]
+    fn test_block_left_and_right_titles() {
+        let mut buffer = Buffer::new(30, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 30, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("File")
+            .title_aligned("[modified]", Alignment::Right);
+
+        block.render(Rect::new(0, 0, 30, 3), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "+- File -------- [modified] -+");
+    }
+
+    #[test]
+    fn test_block_colliding_titles_truncate_lowest_priority() {
+        let mut buffer = Buffer::new(20, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("File")
+            .title_aligned("[modified]", Alignment::Right);
+
+        block.render(Rect::new(0, 0, 20, 3), &mut frame);
+        assert_eq!(row(&buffer, 0), "+- File - [modifie-+");
+
+        // Without room for any of its text, the extra title is dropped.
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("File")
+            .title_aligned("[modified]", Alignment::Right);
+        block.render(Rect::new(0, 0, 11, 3), &mut frame);
+        assert_eq!(&row(&buffer, 0)[..11], "+- File --+");
+    }
+
+    #[test]
+    fn test_block_wide_title_keeps_right_corner() {
+        let mut buffer = Buffer::new(12, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 12, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("ab")
+            .title_aligned("日本", Alignment::Right);
+
+        block.render(Rect::new(0, 0, 12, 3), &mut frame);
+
+        // " 日本 " takes 6 columns, not 4: cut to " 日", it fits between the
+        // left title and the corner. Continuation cells hold '\0'.
+        assert_eq!(row(&buffer, 0), "+- ab - 日\0-+");
+    }
+
+    #[test]
+    fn test_block_wide_titles_centered_and_bottom() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title("日本")
+            .title_alignment(Alignment::Center);
+        block.render(Rect::new(0, 0, 10, 3), &mut frame);
+        assert_eq!(row(&buffer, 0), "+- 日\0本\0 -+");
+
+        // Cut to the 5 columns between the corners' margins, right-aligned.
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title_bottom("日本語")
+            .title_alignment(Alignment::Right);
+        block.render(Rect::new(0, 0, 8, 3), &mut frame);
+        let bottom: String = row(&buffer, 2).chars().take(8).collect();
+        assert_eq!(bottom, "+ 日\0本\0-+");
+    }
+
+    #[test]
+    fn test_block_title_bottom() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii)
+            .title_bottom("Ok");
+
+        block.render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "+--------+");
+        assert_eq!(row(&buffer, 2), "+- Ok ---+");
+    }
+
+    #[test]
+    fn test_block_fill_covers_previous_content() {
+        use crate::Color;
+
+        let mut buffer = Buffer::new(6, 4);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 4));
+        for y in 0..4 {
+            frame.write_str(0, y, "abcdef");
+        }
+        let style = Style::new().bg(Color::Blue);
+        let block = Block::new().borders(Borders::ALL).style(style).fill(true);
+
+        block.render(Rect::new(1, 0, 4, 4), &mut frame);
+
+        for (x, y) in [(2, 1), (3, 1), (2, 2), (3, 2)] {
+            assert_eq!(buffer.get(x, y).symbol, ' ');
+            assert_eq!(buffer.get(x, y).style.background, Some(Color::Blue));
+        }
+        assert_eq!(buffer.get(1, 0).symbol, R_BORDER_TL);
+        assert_eq!(buffer.get(0, 1).symbol, 'a');
+        assert_eq!(buffer.get(5, 1).symbol, 'f');
+    }
+
+    #[test]
+    fn test_block_wrap_draws_inside_border() {
+        use crate::widgets::Text;
+
+        let mut buffer = Buffer::new(8, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 8, 3));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Ascii);
+
+        frame.render_widget(block.wrap(Text::new("inside text")), Rect::new(0, 0, 8, 3));
+
+        assert_eq!(row(&buffer, 0), "+------+");
+        assert_eq!(row(&buffer, 1), "|inside|");
+        assert_eq!(row(&buffer, 2), "+------+");
+    }
+
     #[test]
     fn test_block_inner_area() {
         let block = Block::new().borders(Borders::ALL).padding(0);
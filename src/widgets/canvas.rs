@@ -0,0 +1,228 @@
+//! A free-form drawing surface with sub-cell resolution.
+
+use crate::{Frame, Rect, Style, widgets::Widget};
+
+/// The first braille pattern; the low 8 bits of a glyph select its dots.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// The bit of each dot in a braille cell, indexed as `[row][column]`.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// The closure that draws a [`Canvas`].
+type Paint<'a> = Box<dyn Fn(&mut Painter) + 'a>;
+
+/// A drawing surface in its own coordinate space, plotted with braille
+/// glyphs so each cell holds a 2×4 grid of dots.
+///
+/// `x_bounds` and `y_bounds` give the coordinates of the area's edges; `y`
+/// grows upwards. Shapes are drawn by the closure passed to
+/// [`paint`](Self::paint). Cells without dots are left untouched, so a canvas
+/// can be drawn over other content. In ASCII-only mode every cell with a dot
+/// is drawn as `.`.
+///
+/// ```
+/// use phosphor::widgets::Canvas;
+///
+/// let diagonal = Canvas::new((0.0, 1.0), (0.0, 1.0)).paint(|p| {
+///     p.line(0.0, 0.0, 1.0, 1.0);
+/// });
+/// ```
+pub struct Canvas<'a> {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    painter: Option<Paint<'a>>,
+    style: Style,
+}
+
+impl<'a> Canvas<'a> {
+    /// Creates an empty canvas spanning `x_bounds` (left, right) and
+    /// `y_bounds` (bottom, top).
+    pub fn new(x_bounds: (f64, f64), y_bounds: (f64, f64)) -> Self {
+        Self {
+            x_bounds,
+            y_bounds,
+            painter: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the closure that draws the canvas' content.
+    pub fn paint<F>(mut self, painter: F) -> Self
+    where
+        F: Fn(&mut Painter) + 'a,
+    {
+        self.painter = Some(Box::new(painter));
+        self
+    }
+
+    /// Sets the style of the plotted cells.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for Canvas<'_> {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        let Some(paint) = self.painter else {
+            return;
+        };
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let mut painter = Painter::new(self.x_bounds, self.y_bounds, area.width, area.height);
+        paint(&mut painter);
+
+        let ascii = frame.is_ascii_only();
+        frame.render_area(area, |f| {
+            for (i, &bits) in painter.cells.iter().enumerate() {
+                if bits == 0 {
+                    continue;
+                }
+                let symbol = if ascii {
+                    '.'
+                } else {
+                    char::from_u32(BRAILLE_BLANK + bits as u32).unwrap_or(' ')
+                };
+                let x = (i % area.width as usize) as u16;
+                let y = (i / area.width as usize) as u16;
+                f.write_str_with_style(x, y, symbol.encode_utf8(&mut [0; 4]), self.style);
+            }
+        });
+    }
+}
+
+/// Plots points and lines onto a [`Canvas`], in canvas coordinates.
+///
+/// Coordinates outside the canvas bounds are ignored.
+pub struct Painter {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    /// Size of the area in cells.
+    width: u16,
+    height: u16,
+    /// The braille dot bits of each cell, row-major.
+    cells: Vec<u8>,
+}
+
+impl Painter {
+    fn new(x_bounds: (f64, f64), y_bounds: (f64, f64), width: u16, height: u16) -> Self {
+        Self {
+            x_bounds,
+            y_bounds,
+            width,
+            height,
+            cells: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Sets the dot at `(x, y)`.
+    pub fn point(&mut self, x: f64, y: f64) {
+        if let Some((dx, dy)) = self.to_dots(x, y) {
+            self.set_dot(dx, dy);
+        }
+    }
+
+    /// Draws a straight line from `(x1, y1)` to `(x2, y2)`.
+    ///
+    /// Lines with an end outside the bounds are skipped.
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let (Some((x1, y1)), Some((x2, y2))) = (self.to_dots(x1, y1), self.to_dots(x2, y2)) else {
+            return;
+        };
+
+        // Bresenham, in dot space.
+        let (mut x, mut y) = (x1 as i32, y1 as i32);
+        let (x2, y2) = (x2 as i32, y2 as i32);
+        let dx = (x2 - x).abs();
+        let dy = -(y2 - y).abs();
+        let step_x = if x < x2 { 1 } else { -1 };
+        let step_y = if y < y2 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            self.set_dot(x as u16, y as u16);
+            if x == x2 && y == y2 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Maps canvas coordinates to a dot, counted from the top-left corner.
+    fn to_dots(&self, x: f64, y: f64) -> Option<(u16, u16)> {
+        let (left, right) = self.x_bounds;
+        let (bottom, top) = self.y_bounds;
+        if !(left..=right).contains(&x) || !(bottom..=top).contains(&y) {
+            return None;
+        }
+
+        let dots_wide = (self.width * 2 - 1) as f64;
+        let dots_high = (self.height * 4 - 1) as f64;
+        let dx = if right > left {
+            (x - left) / (right - left) * dots_wide
+        } else {
+            0.0
+        };
+        let dy = if top > bottom {
+            (top - y) / (top - bottom) * dots_high
+        } else {
+            0.0
+        };
+        Some((dx.round() as u16, dy.round() as u16))
+    }
+
+    fn set_dot(&mut self, dx: u16, dy: u16) {
+        let cell = (dy / 4) as usize * self.width as usize + (dx / 2) as usize;
+        self.cells[cell] |= BRAILLE_DOTS[(dy % 4) as usize][(dx % 2) as usize];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    fn render(canvas: Canvas, width: u16, height: u16) -> Buffer {
+        let mut buffer = Buffer::new(width, height);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, width, height));
+        frame.render_widget(canvas, Rect::new(0, 0, width, height));
+        buffer
+    }
+
+    #[test]
+    fn test_canvas_point_sets_braille_bit() {
+        let canvas = Canvas::new((0.0, 10.0), (0.0, 10.0)).paint(|p| {
+            // Top-left and bottom-right corners.
+            p.point(0.0, 10.0);
+            p.point(10.0, 0.0);
+            // Ignored: out of bounds.
+            p.point(11.0, 5.0);
+        });
+        let buffer = render(canvas, 5, 5);
+
+        assert_eq!(buffer.get(0, 0).symbol, '\u{2801}');
+        assert_eq!(buffer.get(4, 4).symbol, '\u{2880}');
+        let plotted = buffer.content.iter().filter(|c| c.symbol != ' ').count();
+        assert_eq!(plotted, 2);
+    }
+
+    #[test]
+    fn test_canvas_line_fills_cells() {
+        let canvas = Canvas::new((0.0, 3.0), (0.0, 1.0)).paint(|p| p.line(0.0, 1.0, 3.0, 1.0));
+        let buffer = render(canvas, 2, 2);
+
+        // The top row of dots in every cell of the first row.
+        assert_eq!(buffer.get(0, 0).symbol, '\u{2809}');
+        assert_eq!(buffer.get(1, 0).symbol, '\u{2809}');
+        assert_eq!(buffer.get(0, 1).symbol, ' ');
+    }
+}
@@ -0,0 +1,61 @@
+//! A widget that blanks its area.
+
+use crate::{Frame, Rect, widgets::Widget};
+
+/// Fills its area with spaces, erasing anything drawn there before.
+///
+/// Render it before a popup so the content behind doesn't show through:
+/// `frame.render_widget(Clear, modal_area)`. The cells take the frame's
+/// current style, so wrap the call in [`Frame::with_style`] to clear to a
+/// background color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Clear;
+
+impl Widget for Clear {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        let blank = " ".repeat(area.width as usize);
+        frame.render_area(area, |f| {
+            for y in 0..f.height() {
+                f.write_str(0, y, &blank);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color, Style};
+
+    #[test]
+    fn test_clear_blanks_area() {
+        let mut buffer = Buffer::new(6, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 2));
+        frame.write_str(0, 0, "abcdef");
+        frame.write_str(0, 1, "ghijkl");
+
+        frame.render_widget(Clear, Rect::new(1, 0, 3, 1));
+
+        assert_eq!(buffer.get(0, 0).symbol, 'a');
+        for x in 1..4 {
+            assert_eq!(buffer.get(x, 0).symbol, ' ');
+        }
+        assert_eq!(buffer.get(4, 0).symbol, 'e');
+        assert_eq!(buffer.get(1, 1).symbol, 'h');
+    }
+
+    #[test]
+    fn test_clear_uses_frame_style() {
+        let mut buffer = Buffer::new(3, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 1));
+        frame.write_str_with_style(0, 0, "abc", Style::new().fg(Color::Red));
+
+        frame.with_style(Style::new().bg(Color::Blue), |f| {
+            f.render_widget(Clear, Rect::new(0, 0, 3, 1));
+        });
+
+        assert_eq!(buffer.get(0, 0).symbol, ' ');
+        assert_eq!(buffer.get(0, 0).style.foreground, None);
+        assert_eq!(buffer.get(0, 0).style.background, Some(Color::Blue));
+    }
+}
@@ -1,46 +1,610 @@
-use crate::{Style, Widget};
+//! A widget that displays a vertical list of selectable items.
 
-pub struct List {
-    items: Vec<String>,
+use crate::{Event, Frame, KeyCode, Rect, Style, Widget, widgets, widgets::WidgetState};
+
+/// Selection and scroll position of a [`List`], kept by the application
+/// across frames.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListState {
     selected: Option<usize>,
+    offset: usize,
+    activated: Option<usize>,
+}
+
+impl ListState {
+    /// Creates a state with nothing selected, scrolled to the top.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the index of the selected entry, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Selects the entry at `index`, or clears the selection.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index;
+    }
+
+    /// Returns the index of the first visible entry.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the entry the last handled event activated, if any.
+    ///
+    /// Activation (Enter) is distinct from selection: moving the selection
+    /// highlights an entry, activating it asks the application to open it.
+    /// Each call to [`handle_event`](Self::handle_event) clears it first.
+    pub fn activated(&self) -> Option<usize> {
+        self.activated
+    }
+
+    /// Updates the state from a key event for a list of `entries`.
+    ///
+    /// Up and Down move the selection, skipping headers like
+    /// [`List::next`] and [`List::previous`]; Enter activates the selected
+    /// entry if it is an item. Returns whether the event was used.
+    pub fn handle_event(&mut self, event: &Event, entries: &[ListEntry]) -> bool {
+        self.activated = None;
+        let Event::Key(key) = event else {
+            return false;
+        };
+        let is_item = |i: usize| matches!(entries.get(i), Some(ListEntry::Item(_)));
+        self.clamp(entries.len());
+
+        match key.code {
+            KeyCode::Up => {
+                let end = self.selected.unwrap_or(entries.len());
+                if let Some(i) = (0..end).rev().find(|&i| is_item(i)) {
+                    self.selected = Some(i);
+                }
+            }
+            KeyCode::Down => {
+                let start = self.selected.map_or(0, |i| i + 1);
+                if let Some(i) = (start..entries.len()).find(|&i| is_item(i)) {
+                    self.selected = Some(i);
+                }
+            }
+            KeyCode::Enter => self.activated = self.selected.filter(|&i| is_item(i)),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl WidgetState for ListState {
+    fn clamp(&mut self, len: usize) {
+        self.selected = match len {
+            0 => None,
+            _ => self.selected.map(|i| i.min(len - 1)),
+        };
+        self.offset = self.offset.min(len.saturating_sub(1));
+        self.activated = self.activated.filter(|&i| i < len);
+    }
+}
+
+/// A row of a [`List`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListEntry {
+    /// A selectable item.
+    Item(String),
+    /// A non-selectable section header, drawn with the list's header style.
+    Header(String),
+}
+
+/// A vertical list of items, one per row, with an optional highlighted selection.
+///
+/// The selection can live in the widget itself ([`selected`](Self::selected)),
+/// or in a [`ListState`] so scrolling persists across frames: pass it to
+/// [`Frame::render_stateful_widget`], or borrow it via [`state`](Self::state).
+pub struct List<'a> {
+    entries: Vec<ListEntry>,
+    selected: Option<usize>,
+    offset: usize,
+    state: Option<&'a mut ListState>,
     style: Style,
+    header_style: Style,
     highlight_style: Style,
     highlight_symbol: Option<String>,
+    highlight_full_width: bool,
 }
 
-impl List {
+impl<'a> List<'a> {
+    /// Creates a new list with the given items.
     pub fn new(items: Vec<String>) -> Self {
+        Self::with_entries(items.into_iter().map(ListEntry::Item).collect())
+    }
+
+    /// Creates a new list from items interspersed with section headers.
+    pub fn with_entries(entries: Vec<ListEntry>) -> Self {
         Self {
-            items,
+            entries,
             selected: None,
+            offset: 0,
+            state: None,
             style: Style::default(),
+            header_style: Style::default(),
             highlight_style: Style::default(),
             highlight_symbol: None,
+            highlight_full_width: false,
         }
     }
 
+    /// Selects the item at the given index.
     pub fn selected(&mut self, index: usize) {
         self.selected = Some(index);
     }
 
+    /// Renders from and updates `state` instead of the widget's own selection.
+    ///
+    /// The list holds the mutable borrow until it is rendered, which consumes
+    /// it, so build the list right before rendering it. Since
+    /// [`Application::draw`] only gets `&self`, keep the state in a `RefCell`
+    /// in the model and pass `&mut state.borrow_mut()`.
+    ///
+    /// [`Application::draw`]: crate::Application::draw
+    pub fn state(mut self, state: &'a mut ListState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Sets the index of the first visible entry, e.g. one saved from
+    /// [`offset`](Self::offset) on the previous frame.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the index of the first visible entry.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Scrolls the minimum amount needed to keep the selected entry within
+    /// `height` visible rows, and returns the new offset.
+    ///
+    /// Scrolls down when the selection passes the bottom and up when it passes
+    /// the top. Headers scroll along with the items.
+    pub fn scroll_into_view(&mut self, height: usize) -> usize {
+        match self.selected {
+            Some(i) if i < self.offset => self.offset = i,
+            Some(i) if i >= self.offset + height => self.offset = i + 1 - height,
+            _ => {}
+        }
+        self.offset
+    }
+
+    /// Returns the index of the selected entry, if any.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Moves the selection to the next item, skipping headers.
+    ///
+    /// Selects the first item if nothing is selected, and stays put at the end.
+    pub fn next(&mut self) {
+        let start = self.selected.map_or(0, |i| i + 1);
+        if let Some(i) = (start..self.entries.len()).find(|&i| self.is_item(i)) {
+            self.selected = Some(i);
+        }
+    }
+
+    /// Moves the selection to the previous item, skipping headers.
+    ///
+    /// Selects the last item if nothing is selected, and stays put at the start.
+    pub fn previous(&mut self) {
+        let end = self.selected.unwrap_or(self.entries.len());
+        if let Some(i) = (0..end).rev().find(|&i| self.is_item(i)) {
+            self.selected = Some(i);
+        }
+    }
+
+    fn is_item(&self, index: usize) -> bool {
+        matches!(self.entries.get(index), Some(ListEntry::Item(_)))
+    }
+
+    /// Sets the style of unselected items.
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
 
+    /// Sets the style of section headers.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Sets the style of the selected item, layered on top of the list
+    /// [`style`](Self::style) with [`Style::patch`].
     pub fn highlight_style(mut self, style: Style) -> Self {
         self.highlight_style = style;
         self
     }
 
+    /// Sets a symbol drawn in front of the selected item (e.g. `"> "`).
+    ///
+    /// Unselected items are indented by the same width so the text stays aligned.
     pub fn highlight_symbol(mut self, symbol: String) -> Self {
         self.highlight_symbol = Some(symbol);
         self
     }
+
+    /// Sets whether the highlight covers the whole row instead of just the item text.
+    pub fn highlight_full_width(mut self, full_width: bool) -> Self {
+        self.highlight_full_width = full_width;
+        self
+    }
 }
 
-impl Widget for List {
-    fn render(self, area: crate::Rect, frame: &mut crate::Frame) {
-        for item in &self.items {}
+impl Widget for List<'_> {
+    /// Renders with the [`state`](List::state) borrowed by the list, or else
+    /// with the widget's own selection and offset.
+    fn render(mut self, area: Rect, frame: &mut Frame) {
+        match self.state.take() {
+            Some(state) => widgets::StatefulWidget::render(self, area, frame, state),
+            None => {
+                let mut state = ListState {
+                    selected: self.selected,
+                    offset: self.offset,
+                    activated: None,
+                };
+                widgets::StatefulWidget::render(self, area, frame, &mut state);
+            }
+        }
+    }
+}
+
+impl widgets::StatefulWidget for List<'_> {
+    type State = ListState;
+
+    /// Draws the list from `state`, clamping it to the entries and scrolling
+    /// its offset to keep the selection visible.
+    fn render(mut self, area: Rect, frame: &mut Frame, state: &mut ListState) {
+        let symbol_width = self
+            .highlight_symbol
+            .as_ref()
+            .map_or(0, |s| s.chars().count());
+        let blank = " ".repeat(symbol_width);
+
+        state.clamp(self.entries.len());
+        self.selected = state.selected;
+        self.offset = state.offset;
+
+        let height = area.height as usize;
+        let offset = self.scroll_into_view(height);
+        state.offset = offset;
+
+        frame.render_area(area, |f| {
+            let visible = self.entries.iter().enumerate().skip(offset).take(height);
+            for (row, (i, entry)) in visible.enumerate() {
+                let y = row as u16;
+                let item = match entry {
+                    ListEntry::Header(title) => {
+                        f.write_str_with_style(0, y, title, self.header_style);
+                        continue;
+                    }
+                    ListEntry::Item(item) => item,
+                };
+                let is_selected = self.selected == Some(i);
+
+                let (style, prefix) = if is_selected {
+                    let prefix = self.highlight_symbol.as_deref().unwrap_or("");
+                    (self.style.patch(self.highlight_style), prefix)
+                } else {
+                    (self.style, blank.as_str())
+                };
+
+                f.with_style(style, |f| {
+                    if is_selected && self.highlight_full_width {
+                        f.write_str(0, y, &" ".repeat(f.width() as usize));
+                    }
+                    f.write_str(0, y, prefix);
+                    f.write_str(symbol_width as u16, y, item);
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color};
+
+    fn items() -> Vec<String> {
+        vec!["One".to_string(), "Two".to_string(), "Three".to_string()]
+    }
+
+    #[test]
+    fn test_list_render_items() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+
+        List::new(items()).render(Rect::new(0, 0, 10, 2), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, 'O');
+        assert_eq!(buffer.get(0, 1).symbol, 'T');
+        assert_eq!(buffer.get(1, 1).symbol, 'w');
+    }
+
+    #[test]
+    fn test_list_render_highlight_symbol() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let mut list = List::new(items())
+            .highlight_symbol("> ".to_string())
+            .highlight_style(Style::new().fg(Color::Red));
+        list.selected(1);
+
+        list.render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, ' ');
+        assert_eq!(buffer.get(2, 0).symbol, 'O');
+        assert_eq!(buffer.get(0, 1).symbol, '>');
+        assert_eq!(buffer.get(2, 1).symbol, 'T');
+        assert_eq!(buffer.get(2, 1).style.foreground, Some(Color::Red));
+        assert_eq!(buffer.get(2, 0).style.foreground, None);
+    }
+
+    #[test]
+    fn test_list_highlight_text_only() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let mut list = List::new(items()).highlight_style(Style::new().bg(Color::Blue));
+        list.selected(0);
+
+        list.render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        assert_eq!(buffer.get(2, 0).style.background, Some(Color::Blue));
+        assert_eq!(buffer.get(3, 0).style.background, None);
+    }
+
+    fn sectioned() -> Vec<ListEntry> {
+        vec![
+            ListEntry::Header("Fruit".to_string()),
+            ListEntry::Item("Apple".to_string()),
+            ListEntry::Header("Veg".to_string()),
+            ListEntry::Item("Leek".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_list_navigation_skips_headers() {
+        let mut list = List::with_entries(sectioned());
+
+        list.next();
+        assert_eq!(list.selected_index(), Some(1));
+        list.next();
+        assert_eq!(list.selected_index(), Some(3));
+        list.next();
+        assert_eq!(list.selected_index(), Some(3));
+        list.previous();
+        assert_eq!(list.selected_index(), Some(1));
+        list.previous();
+        assert_eq!(list.selected_index(), Some(1));
+    }
+
+    #[test]
+    fn test_list_header_style() {
+        let mut buffer = Buffer::new(10, 4);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 4));
+        let list = List::with_entries(sectioned())
+            .header_style(Style::new().fg(Color::Yellow))
+            .style(Style::new().fg(Color::White));
+
+        list.render(Rect::new(0, 0, 10, 4), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, 'F');
+        assert_eq!(buffer.get(0, 0).style.foreground, Some(Color::Yellow));
+        assert_eq!(buffer.get(0, 1).symbol, 'A');
+        assert_eq!(buffer.get(0, 1).style.foreground, Some(Color::White));
+    }
+
+    #[test]
+    fn test_list_scrolls_selection_into_view() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let mut list = List::with_entries(sectioned());
+        list.selected(3);
+
+        list.render(Rect::new(0, 0, 10, 2), &mut frame);
+
+        // Rows 2..4 are visible: the "Veg" header then "Leek".
+        assert_eq!(buffer.get(0, 0).symbol, 'V');
+        assert_eq!(buffer.get(0, 1).symbol, 'L');
+    }
+
+    fn long_list(selected: usize) -> List<'static> {
+        let items = (0..20).map(|i| format!("Item {i}")).collect();
+        let mut list = List::new(items);
+        list.selected(selected);
+        list
+    }
+
+    #[test]
+    fn test_list_scroll_first_item() {
+        let mut list = long_list(0);
+        assert_eq!(list.scroll_into_view(10), 0);
+    }
+
+    #[test]
+    fn test_list_scroll_mid_list() {
+        let mut list = long_list(7).with_offset(12);
+        // Selection above the viewport scrolls up to it.
+        assert_eq!(list.scroll_into_view(10), 7);
+        // Moving within the viewport keeps the offset.
+        list.selected(12);
+        assert_eq!(list.scroll_into_view(10), 7);
+    }
+
+    #[test]
+    fn test_list_scroll_last_item_renders_visible() {
+        let mut buffer = Buffer::new(10, 10);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 10));
+        let mut list = long_list(19);
+        assert_eq!(list.scroll_into_view(10), 10);
+
+        list.render(Rect::new(0, 0, 10, 10), &mut frame);
+
+        // Items 0..10 are scrolled off; the last row shows "Item 19".
+        assert_eq!(buffer.get(5, 0).symbol, '1');
+        assert_eq!(buffer.get(6, 0).symbol, '0');
+        assert_eq!(buffer.get(5, 9).symbol, '1');
+        assert_eq!(buffer.get(6, 9).symbol, '9');
+    }
+
+    #[test]
+    fn test_list_state_updates_offset() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let mut state = ListState::new();
+        state.select(Some(2));
+
+        List::new(items())
+            .state(&mut state)
+            .render(Rect::new(0, 0, 10, 2), &mut frame);
+
+        assert_eq!(state.offset(), 1);
+        assert_eq!(buffer.get(0, 0).symbol, 'T');
+        assert_eq!(buffer.get(1, 1).symbol, 'h');
+    }
+
+    #[test]
+    fn test_list_stateful_offset_persists() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let area = Rect::new(0, 0, 10, 2);
+        let mut state = ListState::new();
+
+        state.select(Some(2));
+        frame.render_stateful_widget(List::new(items()), area, &mut state);
+        assert_eq!(state.offset(), 1);
+
+        // Moving up within the viewport keeps the saved offset.
+        state.select(Some(1));
+        frame.render_stateful_widget(List::new(items()), area, &mut state);
+        assert_eq!(state.offset(), 1);
+        assert_eq!(buffer.get(0, 0).symbol, 'T');
+        assert_eq!(buffer.get(1, 1).symbol, 'h');
+    }
+
+    #[test]
+    fn test_list_highlight_patches_item_style() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let mut list = List::new(items())
+            .style(Style::new().fg(Color::Green))
+            .highlight_style(Style::new().bg(Color::Blue));
+        list.selected(0);
+
+        list.render(Rect::new(0, 0, 10, 2), &mut frame);
+
+        let cell = buffer.get(0, 0);
+        assert_eq!(cell.style.foreground, Some(Color::Green));
+        assert_eq!(cell.style.background, Some(Color::Blue));
+        assert_eq!(buffer.get(0, 1).style.background, None);
+    }
+
+    #[test]
+    fn test_list_state_clamped_to_items() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let mut state = ListState::new();
+        state.select(Some(10));
+
+        List::new(items())
+            .state(&mut state)
+            .render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        assert_eq!(state.selected(), Some(2));
+        assert_eq!(state.offset(), 0);
+    }
+
+    #[test]
+    fn test_list_highlight_full_width() {
+        let mut buffer = Buffer::new(10, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 3));
+        let mut list = List::new(items())
+            .highlight_style(Style::new().bg(Color::Blue))
+            .highlight_full_width(true);
+        list.selected(1);
+
+        list.render(Rect::new(0, 0, 10, 3), &mut frame);
+
+        for x in 3..10 {
+            assert_eq!(buffer.get(x, 1).symbol, ' ');
+            assert_eq!(buffer.get(x, 1).style.background, Some(Color::Blue));
+        }
+        // Other rows are untouched
+        assert_eq!(buffer.get(5, 0).style.background, None);
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(crate::KeyEvent::new(code))
+    }
+
+    fn entries(len: usize) -> Vec<ListEntry> {
+        (0..len).map(|i| ListEntry::Item(i.to_string())).collect()
+    }
+
+    #[test]
+    fn test_list_state_navigation_does_not_activate() {
+        let mut state = ListState::new();
+
+        assert!(state.handle_event(&key(KeyCode::Down), &entries(3)));
+        assert!(state.handle_event(&key(KeyCode::Down), &entries(3)));
+        assert_eq!(state.selected(), Some(1));
+        assert_eq!(state.activated(), None);
+
+        assert!(state.handle_event(&key(KeyCode::Up), &entries(3)));
+        assert!(state.handle_event(&key(KeyCode::Up), &entries(3)));
+        assert_eq!(state.selected(), Some(0));
+        assert!(!state.handle_event(&key(KeyCode::Char('x')), &entries(3)));
+    }
+
+    #[test]
+    fn test_list_state_enter_activates_selection() {
+        let mut state = ListState::new();
+        state.select(Some(2));
+
+        state.handle_event(&key(KeyCode::Enter), &entries(3));
+        assert_eq!(state.activated(), Some(2));
+        assert_eq!(state.selected(), Some(2));
+
+        // The activation only lasts until the next event.
+        state.handle_event(&key(KeyCode::Up), &entries(3));
+        assert_eq!(state.activated(), None);
+    }
+
+    #[test]
+    fn test_list_state_skips_headers() {
+        let entries = vec![
+            ListEntry::Header("Fruit".into()),
+            ListEntry::Item("apple".into()),
+            ListEntry::Header("Veg".into()),
+            ListEntry::Item("leek".into()),
+        ];
+        let mut state = ListState::new();
+
+        state.handle_event(&key(KeyCode::Down), &entries);
+        assert_eq!(state.selected(), Some(1));
+        state.handle_event(&key(KeyCode::Down), &entries);
+        assert_eq!(state.selected(), Some(3));
+        state.handle_event(&key(KeyCode::Up), &entries);
+        assert_eq!(state.selected(), Some(1));
+        // Nothing above but a header: the selection stays put.
+        state.handle_event(&key(KeyCode::Up), &entries);
+        assert_eq!(state.selected(), Some(1));
+
+        state.handle_event(&key(KeyCode::Enter), &entries);
+        assert_eq!(state.activated(), Some(1));
+
+        // A header is never activated, even if selected directly.
+        state.select(Some(2));
+        state.handle_event(&key(KeyCode::Enter), &entries);
+        assert_eq!(state.activated(), None);
     }
 }
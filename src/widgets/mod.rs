@@ -3,17 +3,54 @@
 use crate::{Frame, Rect};
 
 pub mod block;
+pub mod canvas;
+pub mod clear;
 pub mod list;
+pub mod paragraph;
+pub mod rule;
 pub mod scrollable;
+pub mod scrollbar;
+pub mod sparkline;
+pub mod tabs;
 pub mod text;
+pub mod text_input;
 
-pub use block::{Block, BorderType, Borders};
-pub use list::List;
+pub use block::{Block, BorderType, Borders, Wrapped};
+pub use canvas::{Canvas, Painter};
+pub use clear::Clear;
+pub use list::{List, ListEntry, ListState};
+pub use paragraph::{Paragraph, Wrap};
+pub use rule::{HRule, VRule};
 pub use scrollable::Scrollable;
-pub use text::Text;
+pub use scrollbar::Scrollbar;
+pub use sparkline::Sparkline;
+pub use tabs::Tabs;
+pub use text::{Line, Span, Text};
+pub use text_input::{TextInput, TextInputState};
+
+/// State that a widget reads and updates while rendering, owned by the
+/// application so it persists across frames (e.g. [`ListState`]).
+pub trait WidgetState {
+    /// Brings the state back in range for a widget with `len` entries, e.g.
+    /// after items were removed.
+    fn clamp(&mut self, len: usize);
+}
 
 /// The core trait for all UI components.
 pub trait Widget {
     /// Draws the widget into the given area of the frame.
     fn render(self, area: Rect, frame: &mut Frame);
 }
+
+/// A widget that renders from, and updates, state owned by the caller.
+///
+/// The widget itself is rebuilt and consumed every frame, while the state
+/// (selection, scroll offset, ...) lives in the application model and persists.
+/// Render it with [`Frame::render_stateful_widget`].
+pub trait StatefulWidget {
+    /// The state the widget reads and updates.
+    type State;
+
+    /// Draws the widget into the given area of the frame, updating `state`.
+    fn render(self, area: Rect, frame: &mut Frame, state: &mut Self::State);
+}
@@ -0,0 +1,277 @@
+//! A widget that displays a block of text wrapped to the width of its area.
+
+use crate::{
+    Alignment, Frame, Rect, Style,
+    buffer::char_width,
+    widgets::{
+        Line, Span, Widget,
+        text::{StyledChar, columns, draw_row},
+    },
+};
+
+/// Controls how [`Paragraph`] treats whitespace when it wraps a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wrap {
+    /// Drops leading whitespace on rows created by wrapping.
+    pub trim: bool,
+}
+
+/// A block of text wrapped at word boundaries to fit its area.
+///
+/// Words longer than the area are broken by character. Rows that don't fit
+/// the area's height are clipped.
+pub struct Paragraph {
+    lines: Vec<Line>,
+    style: Style,
+    alignment: Alignment,
+    wrap: Wrap,
+}
+
+impl Paragraph {
+    /// Creates a new paragraph. Newlines in `text` start new rows.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self::from_lines(vec![Line::raw(text)])
+    }
+
+    /// Creates a paragraph from styled spans, drawn one after the other.
+    ///
+    /// Each span's style is layered on top of the paragraph
+    /// [`style`](Self::style); wrapping may break a row inside a span.
+    pub fn from_spans(spans: Vec<Span>) -> Self {
+        Self::from_lines(vec![Line::from_spans(spans)])
+    }
+
+    /// Creates a paragraph from lines, each starting on a new row.
+    ///
+    /// A line's own alignment overrides the paragraph's.
+    pub fn from_lines(lines: Vec<Line>) -> Self {
+        Self {
+            lines,
+            style: Style::default(),
+            alignment: Alignment::Left,
+            wrap: Wrap { trim: true },
+        }
+    }
+
+    /// Sets the style of the text.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the horizontal alignment of each row (default: left).
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets the wrapping behavior (default: `Wrap { trim: true }`).
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+}
+
+/// Splits a line into alternating runs of whitespace and non-whitespace.
+fn tokens(line: &[StyledChar]) -> Vec<&[StyledChar]> {
+    line.chunk_by(|(a, _), (b, _)| a.is_whitespace() == b.is_whitespace())
+        .collect()
+}
+
+/// Wraps a line without newlines into rows at most `width` columns wide.
+fn wrap_line(line: &[StyledChar], width: usize, trim: bool) -> Vec<Vec<StyledChar>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<StyledChar> = Vec::new();
+    let mut wrapped = false;
+
+    for token in tokens(line) {
+        let is_space = token[0].0.is_whitespace();
+
+        if columns(&row) + columns(token) <= width {
+            // Skip whitespace left at the start of a wrapped row.
+            if !(is_space && trim && wrapped && row.is_empty()) {
+                row.extend_from_slice(token);
+            }
+            continue;
+        }
+
+        if !row.is_empty() {
+            let mut full = std::mem::take(&mut row);
+            if trim {
+                while full.last().is_some_and(|(c, _)| c.is_whitespace()) {
+                    full.pop();
+                }
+            }
+            rows.push(full);
+        }
+        wrapped = true;
+        if is_space && trim {
+            continue;
+        }
+
+        // Break overly long tokens by character.
+        for &c in token {
+            if !row.is_empty() && columns(&row) + char_width(c.0) as usize > width {
+                rows.push(std::mem::take(&mut row));
+            }
+            row.push(c);
+        }
+    }
+
+    rows.push(row);
+    rows
+}
+
+impl Widget for Paragraph {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        let width = area.width as usize;
+        if width == 0 {
+            return;
+        }
+
+        let mut rows: Vec<(Vec<StyledChar>, Alignment)> = Vec::new();
+        for line in &self.lines {
+            let alignment = line.alignment.unwrap_or(self.alignment);
+            let chars: Vec<StyledChar> = line
+                .spans
+                .iter()
+                .flat_map(|span| {
+                    let style = self.style.patch(span.style);
+                    span.content.chars().map(move |c| (c, style))
+                })
+                .collect();
+            for paragraph in chars.split(|(c, _)| *c == '\n') {
+                let wrapped = wrap_line(paragraph, width, self.wrap.trim);
+                rows.extend(wrapped.into_iter().map(|row| (row, alignment)));
+            }
+        }
+
+        frame.render_area(area, |f| {
+            for (y, (row, alignment)) in rows.iter().enumerate().take(f.height() as usize) {
+                let len = columns(row).min(width) as u16;
+                let x = match alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => (f.width() - len) / 2,
+                    Alignment::Right => f.width() - len,
+                };
+                draw_row(f, x, y as u16, row);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color};
+
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.width).map(|x| buffer.get(x, y).symbol).collect()
+    }
+
+    fn wrap_lines(text: &str, width: usize, trim: bool) -> Vec<String> {
+        let chars: Vec<StyledChar> = text.chars().map(|c| (c, Style::default())).collect();
+        wrap_line(&chars, width, trim)
+            .into_iter()
+            .map(|row| row.into_iter().map(|(c, _)| c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_paragraph_wrap_points() {
+        assert_eq!(
+            wrap_lines("the quick brown fox", 10, true),
+            vec!["the quick", "brown fox"]
+        );
+        // Long words are broken by character.
+        assert_eq!(
+            wrap_lines("abcdefgh ij", 4, true),
+            vec!["abcd", "efgh", "ij"]
+        );
+        // Without trimming, the break's whitespace starts the next row.
+        assert_eq!(wrap_lines("ab cd ef", 5, true), vec!["ab cd", "ef"]);
+        assert_eq!(wrap_lines("ab cd ef", 5, false), vec!["ab cd", " ef"]);
+    }
+
+    #[test]
+    fn test_paragraph_centered() {
+        let mut buffer = Buffer::new(8, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 8, 2));
+
+        Paragraph::new("hi there")
+            .alignment(Alignment::Center)
+            .render(Rect::new(0, 0, 6, 2), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "  hi    ");
+        assert_eq!(row(&buffer, 1), "there   ");
+    }
+
+    #[test]
+    fn test_paragraph_clips_to_height() {
+        let mut buffer = Buffer::new(5, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 3));
+
+        Paragraph::new("one two three four").render(Rect::new(0, 0, 5, 2), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "one  ");
+        assert_eq!(row(&buffer, 1), "two  ");
+        assert_eq!(row(&buffer, 2), "     ");
+    }
+
+    #[test]
+    fn test_paragraph_span_colors() {
+        let mut buffer = Buffer::new(12, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 12, 1));
+        let (red, green, blue) = (
+            Style::fg_color(Color::Red),
+            Style::fg_color(Color::Green),
+            Style::fg_color(Color::Blue),
+        );
+
+        Paragraph::from_spans(vec![
+            Span::styled("let ", red),
+            Span::styled("x", green),
+            Span::styled(" = 1;", blue),
+        ])
+        .render(Rect::new(0, 0, 12, 1), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "let x = 1;  ");
+        let colors: Vec<_> = (0..10).map(|x| buffer.get(x, 0).style).collect();
+        assert_eq!(colors[..4], [red; 4]);
+        assert_eq!(colors[4], green);
+        assert_eq!(colors[5..], [blue; 5]);
+        assert_eq!(buffer.get(10, 0).style, Style::default());
+    }
+
+    #[test]
+    fn test_paragraph_spans_keep_style_across_wrap() {
+        let mut buffer = Buffer::new(5, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 2));
+        let bold = Style::bold();
+
+        Paragraph::from_spans(vec![Span::raw("ab "), Span::styled("cd ef", bold)])
+            .render(Rect::new(0, 0, 5, 2), &mut frame);
+
+        assert_eq!(row(&buffer, 0), "ab cd");
+        assert_eq!(row(&buffer, 1), "ef   ");
+        assert_eq!(buffer.get(1, 0).style, Style::default());
+        assert_eq!(buffer.get(3, 0).style, bold);
+        assert_eq!(buffer.get(0, 1).style, bold);
+    }
+
+    #[test]
+    fn test_paragraph_wide_chars_wrap_by_columns() {
+        let mut buffer = Buffer::new(5, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 3));
+
+        Paragraph::new("世界 世界世")
+            .alignment(Alignment::Right)
+            .render(Rect::new(0, 0, 5, 3), &mut frame);
+
+        // Continuation cells hold '\0'; drop them to read the rows as text.
+        let text = |y| row(&buffer, y).replace('\0', "");
+        assert_eq!(text(0), " 世界");
+        assert_eq!(text(1), " 世界");
+        assert_eq!(text(2), "   世");
+    }
+}
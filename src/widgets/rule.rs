@@ -0,0 +1,199 @@
+//! Thin horizontal and vertical separator lines.
+
+use crate::{Frame, Rect, Style, widgets::BorderType, widgets::Widget};
+
+/// A horizontal line spanning the width of its area, with an optional centered label.
+///
+/// Useful as a divider between stacked panels (e.g. `───── Section ─────`).
+pub struct HRule {
+    label: Option<String>,
+    border_type: BorderType,
+    style: Style,
+    label_style: Style,
+}
+
+impl Default for HRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HRule {
+    pub fn new() -> Self {
+        Self {
+            label: None,
+            border_type: BorderType::Plain,
+            style: Style::default(),
+            label_style: Style::default(),
+        }
+    }
+
+    /// Sets a label drawn in the middle of the line.
+    pub fn label<S: Into<String>>(mut self, label: S) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the border type whose horizontal glyph is used for the line.
+    pub fn border_type(mut self, border_type: BorderType) -> Self {
+        self.border_type = border_type;
+        self
+    }
+
+    /// Sets the style of the line.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the label.
+    ///
+    /// If not set, the label uses the line style.
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = style;
+        self
+    }
+}
+
+impl Widget for HRule {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let (h, ..) = self.border_type.resolve(frame).get_chars();
+        frame.with_style(self.style, |f| {
+            f.render_area(area, |f| {
+                let width = f.width();
+                let mut buf = [0u8; 4];
+                let s = h.encode_utf8(&mut buf);
+                for x in 0..width {
+                    f.write_str(x, 0, s);
+                }
+
+                if let Some(label) = self.label {
+                    let label: String = format!(" {} ", label)
+                        .chars()
+                        .take(width as usize)
+                        .collect();
+                    let len = label.chars().count() as u16;
+                    let x = (width - len) / 2;
+
+                    let style = if self.label_style == Style::default() {
+                        self.style
+                    } else {
+                        self.label_style
+                    };
+                    f.write_str_with_style(x, 0, &label, style);
+                }
+            });
+        });
+    }
+}
+
+/// A vertical line spanning the height of its area.
+///
+/// Useful as a divider between side-by-side panels.
+pub struct VRule {
+    border_type: BorderType,
+    style: Style,
+}
+
+impl Default for VRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VRule {
+    pub fn new() -> Self {
+        Self {
+            border_type: BorderType::Plain,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the border type whose vertical glyph is used for the line.
+    pub fn border_type(mut self, border_type: BorderType) -> Self {
+        self.border_type = border_type;
+        self
+    }
+
+    /// Sets the style of the line.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for VRule {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let (_, v, ..) = self.border_type.resolve(frame).get_chars();
+        frame.with_style(self.style, |f| {
+            f.render_area(area, |f| {
+                let mut buf = [0u8; 4];
+                let s = v.encode_utf8(&mut buf);
+                for y in 0..f.height() {
+                    f.write_str(0, y, s);
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color};
+
+    #[test]
+    fn test_hrule_fills_row() {
+        let mut buffer = Buffer::new(6, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 1));
+
+        HRule::new().render(Rect::new(0, 0, 6, 1), &mut frame);
+
+        for x in 0..6 {
+            assert_eq!(buffer.get(x, 0).symbol, '\u{2500}');
+        }
+    }
+
+    #[test]
+    fn test_hrule_centered_label() {
+        let mut buffer = Buffer::new(10, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+        let rule = HRule::new()
+            .label("Hi")
+            .label_style(Style::new().fg(Color::Red));
+
+        rule.render(Rect::new(0, 0, 10, 1), &mut frame);
+
+        // " Hi " is 4 wide, centered in 10 -> starts at x=3
+        assert_eq!(buffer.get(2, 0).symbol, '\u{2500}');
+        assert_eq!(buffer.get(3, 0).symbol, ' ');
+        assert_eq!(buffer.get(4, 0).symbol, 'H');
+        assert_eq!(buffer.get(5, 0).symbol, 'i');
+        assert_eq!(buffer.get(6, 0).symbol, ' ');
+        assert_eq!(buffer.get(7, 0).symbol, '\u{2500}');
+        assert_eq!(buffer.get(4, 0).style.foreground, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_vrule_fills_column() {
+        let mut buffer = Buffer::new(3, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 3));
+
+        VRule::new()
+            .border_type(BorderType::Double)
+            .render(Rect::new(1, 0, 1, 3), &mut frame);
+
+        for y in 0..3 {
+            assert_eq!(buffer.get(1, y).symbol, '\u{2551}');
+            assert_eq!(buffer.get(0, y).symbol, ' ');
+        }
+    }
+}
@@ -9,6 +9,8 @@ pub struct Scrollable<W> {
     y: u16,
     width: u16,
     height: u16,
+    viewport_height: u16,
+    scroll_step: u16,
 }
 
 impl<W> Scrollable<W> {
@@ -22,6 +24,8 @@ impl<W> Scrollable<W> {
             y: 0,
             width: 100,
             height: 100,
+            viewport_height: 0,
+            scroll_step: 1,
         }
     }
 
@@ -38,6 +42,59 @@ impl<W> Scrollable<W> {
         self.height = height;
         self
     }
+
+    /// Sets the height of the area the content will be shown in.
+    ///
+    /// This is the page size for [`page_down`](Self::page_down) and
+    /// [`page_up`](Self::page_up), and bounds scrolling so the last page stays
+    /// full.
+    pub fn viewport_height(mut self, height: u16) -> Self {
+        self.viewport_height = height;
+        self
+    }
+
+    /// Sets how many rows one step of [`scroll_by`](Self::scroll_by) moves
+    /// (default 1).
+    pub fn scroll_step(mut self, step: u16) -> Self {
+        self.scroll_step = step;
+        self
+    }
+
+    /// Returns the current scroll offset as `(x, y)`.
+    pub fn offset(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    /// Returns the number of rows in a page, i.e. the viewport height.
+    pub fn page_size(&self) -> u16 {
+        self.viewport_height
+    }
+
+    /// Scrolls vertically by `delta` steps; negative values scroll up.
+    ///
+    /// The offset is clamped between the top and the last full page.
+    pub fn scroll_by(self, delta: i32) -> Self {
+        let rows = delta.saturating_mul(self.scroll_step as i32);
+        self.scroll_rows(rows)
+    }
+
+    /// Scrolls down by one page.
+    pub fn page_down(self) -> Self {
+        let rows = self.page_size() as i32;
+        self.scroll_rows(rows)
+    }
+
+    /// Scrolls up by one page.
+    pub fn page_up(self) -> Self {
+        let rows = self.page_size() as i32;
+        self.scroll_rows(-rows)
+    }
+
+    fn scroll_rows(mut self, rows: i32) -> Self {
+        let max = self.height.saturating_sub(self.viewport_height) as i32;
+        self.y = (self.y as i32).saturating_add(rows).clamp(0, max) as u16;
+        self
+    }
 }
 
 impl<W: Widget> Widget for Scrollable<W> {
@@ -85,4 +142,27 @@ mod tests {
         assert_eq!(buffer.get(1, 0).symbol, 'o');
         assert_eq!(buffer.get(4, 0).symbol, 'd');
     }
+
+    fn tall() -> Scrollable<Text> {
+        Scrollable::new(Text::new(""))
+            .virtual_size(10, 50)
+            .viewport_height(10)
+    }
+
+    #[test]
+    fn test_scrollable_page_down() {
+        let scrollable = tall().page_down();
+        assert_eq!(scrollable.offset(), (0, 10));
+
+        let scrollable = scrollable.page_down().page_up();
+        assert_eq!(scrollable.offset(), (0, 10));
+    }
+
+    #[test]
+    fn test_scrollable_clamps_to_bounds() {
+        // 50 rows with a 10-row viewport: the last page starts at 40.
+        assert_eq!(tall().scroll_by(100).offset(), (0, 40));
+        assert_eq!(tall().scroll_by(-3).offset(), (0, 0));
+        assert_eq!(tall().scroll_step(3).scroll_by(2).offset(), (0, 6));
+    }
 }
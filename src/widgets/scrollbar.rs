@@ -0,0 +1,162 @@
+//! A vertical scrollbar showing which part of the content is visible.
+
+use crate::{Frame, Rect, Style, widgets::Widget};
+
+const TRACK: char = '\u{2502}';
+const THUMB: char = '\u{2588}';
+const ASCII_TRACK: char = '|';
+const ASCII_THUMB: char = '#';
+
+/// A one-column scrollbar drawn along the right (or left) edge of its area.
+///
+/// The thumb's size is the visible fraction of the content, and its offset
+/// follows `position`, the index of the first visible row.
+pub struct Scrollbar {
+    content_length: usize,
+    viewport_length: usize,
+    position: usize,
+    on_left: bool,
+    track_symbol: Option<char>,
+    thumb_symbol: Option<char>,
+    track_style: Style,
+    thumb_style: Style,
+}
+
+impl Scrollbar {
+    /// Creates a scrollbar for `content_length` rows, `viewport_length` of
+    /// which are visible from `position` on.
+    pub fn new(content_length: usize, viewport_length: usize, position: usize) -> Self {
+        Self {
+            content_length,
+            viewport_length,
+            position,
+            on_left: false,
+            track_symbol: None,
+            thumb_symbol: None,
+            track_style: Style::default(),
+            thumb_style: Style::default(),
+        }
+    }
+
+    /// Draws the scrollbar in the leftmost column instead of the rightmost.
+    pub fn on_left(mut self, on_left: bool) -> Self {
+        self.on_left = on_left;
+        self
+    }
+
+    /// Sets the glyph of the track (default `│`, or `|` in ASCII mode).
+    pub fn track_symbol(mut self, symbol: char) -> Self {
+        self.track_symbol = Some(symbol);
+        self
+    }
+
+    /// Sets the glyph of the thumb (default `█`, or `#` in ASCII mode).
+    pub fn thumb_symbol(mut self, symbol: char) -> Self {
+        self.thumb_symbol = Some(symbol);
+        self
+    }
+
+    /// Sets the style of the track.
+    pub fn track_style(mut self, style: Style) -> Self {
+        self.track_style = style;
+        self
+    }
+
+    /// Sets the style of the thumb.
+    pub fn thumb_style(mut self, style: Style) -> Self {
+        self.thumb_style = style;
+        self
+    }
+
+    /// Returns the thumb's first row and length for a track `height` rows tall.
+    fn thumb(&self, height: usize) -> (usize, usize) {
+        if self.content_length <= self.viewport_length {
+            return (0, height);
+        }
+
+        let len = (height * self.viewport_length / self.content_length).clamp(1, height);
+        let max_position = self.content_length - self.viewport_length;
+        let position = self.position.min(max_position);
+        let free = height - len;
+        // Round to the nearest row so the middle of the content centers it.
+        let start = (free * position + max_position / 2) / max_position;
+        (start, len)
+    }
+}
+
+impl Widget for Scrollbar {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let ascii = frame.is_ascii_only();
+        let track = self
+            .track_symbol
+            .unwrap_or(if ascii { ASCII_TRACK } else { TRACK });
+        let thumb = self
+            .thumb_symbol
+            .unwrap_or(if ascii { ASCII_THUMB } else { THUMB });
+        let (start, len) = self.thumb(area.height as usize);
+        let x = if self.on_left { 0 } else { area.width - 1 };
+
+        frame.render_area(area, |f| {
+            let mut buf = [0u8; 4];
+            for y in 0..f.height() {
+                let (symbol, style) = if (start..start + len).contains(&(y as usize)) {
+                    (thumb, self.thumb_style)
+                } else {
+                    (track, self.track_style)
+                };
+                f.write_str_with_style(x, y, symbol.encode_utf8(&mut buf), style);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    /// Renders into a 2x10 area and returns the rows holding the thumb.
+    fn thumb_rows(scrollbar: Scrollbar, column: u16) -> Vec<u16> {
+        let mut buffer = Buffer::new(2, 10);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 2, 10));
+        scrollbar.render(Rect::new(0, 0, 2, 10), &mut frame);
+        (0..10)
+            .filter(|&y| buffer.get(column, y).symbol == THUMB)
+            .collect()
+    }
+
+    #[test]
+    fn test_scrollbar_thumb_positions() {
+        // Content three times the viewport: the thumb is a third of the track.
+        assert_eq!(thumb_rows(Scrollbar::new(30, 10, 0), 1), vec![0, 1, 2]);
+        assert_eq!(thumb_rows(Scrollbar::new(30, 10, 10), 1), vec![4, 5, 6]);
+        assert_eq!(thumb_rows(Scrollbar::new(30, 10, 20), 1), vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn test_scrollbar_clamps_position() {
+        assert_eq!(thumb_rows(Scrollbar::new(30, 10, 500), 1), vec![7, 8, 9]);
+        // Content that fits fills the whole track.
+        assert_eq!(thumb_rows(Scrollbar::new(5, 10, 3), 1).len(), 10);
+    }
+
+    #[test]
+    fn test_scrollbar_on_left_with_custom_glyphs() {
+        let mut buffer = Buffer::new(3, 4);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 4));
+
+        Scrollbar::new(8, 4, 0)
+            .on_left(true)
+            .track_symbol('.')
+            .thumb_symbol('o')
+            .render(Rect::new(0, 0, 3, 4), &mut frame);
+
+        let column: String = (0..4).map(|y| buffer.get(0, y).symbol).collect();
+        assert_eq!(column, "oo..");
+        assert_eq!(buffer.get(2, 0).symbol, ' ');
+    }
+}
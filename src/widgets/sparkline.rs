@@ -0,0 +1,104 @@
+//! A one-row chart of a series of values.
+
+use crate::{Frame, Rect, Style, widgets::Widget};
+
+/// Block glyphs from one eighth to a full cell tall.
+const BARS: [char; 8] = [
+    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
+];
+
+/// A single-row trend line, one sample per column, drawn with block glyphs.
+///
+/// Samples are scaled so the largest (or the [`max`](Self::max) set by the
+/// caller) fills a whole cell; zero is drawn as a blank. When there are more
+/// samples than columns, the most recent ones are shown.
+pub struct Sparkline<'a> {
+    data: &'a [u64],
+    max: Option<u64>,
+    style: Style,
+}
+
+impl<'a> Sparkline<'a> {
+    /// Creates a sparkline of `data`, oldest sample first.
+    pub fn new(data: &'a [u64]) -> Self {
+        Self {
+            data,
+            max: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the value drawn as a full cell, instead of the data's maximum.
+    ///
+    /// Larger samples are drawn as full cells too.
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the style of the bars.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for Sparkline<'_> {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let skip = self.data.len().saturating_sub(area.width as usize);
+        let samples = &self.data[skip..];
+        let max = self
+            .max
+            .unwrap_or_else(|| samples.iter().copied().max().unwrap_or(0));
+
+        let bars: String = samples
+            .iter()
+            .map(|&value| {
+                if max == 0 || value == 0 {
+                    return ' ';
+                }
+                // Round up so any non-zero sample gets at least one eighth.
+                let level = (value.min(max) * 8).div_ceil(max) as usize;
+                BARS[level - 1]
+            })
+            .collect();
+
+        frame.render_area(area, |f| f.write_str_with_style(0, 0, &bars, self.style));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    fn render(sparkline: Sparkline, width: u16) -> String {
+        let mut buffer = Buffer::new(width, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, width, 1));
+        sparkline.render(Rect::new(0, 0, width, 1), &mut frame);
+        (0..width).map(|x| buffer.get(x, 0).symbol).collect()
+    }
+
+    #[test]
+    fn test_sparkline_flat_series() {
+        assert_eq!(render(Sparkline::new(&[5, 5, 5, 5]), 4), "████");
+        assert_eq!(render(Sparkline::new(&[5, 5]).max(10), 2), "▄▄");
+    }
+
+    #[test]
+    fn test_sparkline_ramp() {
+        let data = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(render(Sparkline::new(&data), 9), " ▁▂▃▄▅▆▇█");
+    }
+
+    #[test]
+    fn test_sparkline_shows_latest_samples() {
+        // Only the last three fit; they're scaled to their own maximum.
+        let data = [100, 100, 2, 4, 8];
+        assert_eq!(render(Sparkline::new(&data), 3), "▂▄█");
+    }
+}
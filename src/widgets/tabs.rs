@@ -0,0 +1,149 @@
+//! A one-row tab bar for switching between views.
+
+use crate::{Frame, Rect, Style, buffer::char_width, widgets::Widget};
+
+/// A row of tab titles with the selected one highlighted.
+///
+/// Titles are padded with a space on each side and separated by a divider.
+/// When they don't fit, the row is clipped, optionally ending in `…`.
+pub struct Tabs {
+    titles: Vec<String>,
+    selected: usize,
+    divider: String,
+    style: Style,
+    highlight_style: Style,
+    ellipsis: bool,
+}
+
+impl Tabs {
+    /// Creates a tab bar with the first tab selected.
+    pub fn new(titles: Vec<String>) -> Self {
+        Self {
+            titles,
+            selected: 0,
+            divider: "\u{2502}".to_string(),
+            style: Style::default(),
+            highlight_style: Style::default(),
+            ellipsis: false,
+        }
+    }
+
+    /// Selects the tab at `index`.
+    pub fn select(mut self, index: usize) -> Self {
+        self.selected = index;
+        self
+    }
+
+    /// Sets the string drawn between titles (default `│`).
+    pub fn divider<S: Into<String>>(mut self, divider: S) -> Self {
+        self.divider = divider.into();
+        self
+    }
+
+    /// Sets the style of the bar and the unselected titles.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the selected title, layered on top of the bar
+    /// [`style`](Self::style).
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Sets whether a clipped row ends in `…` (default: false).
+    pub fn ellipsis(mut self, ellipsis: bool) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+}
+
+fn str_width(s: &str) -> u16 {
+    s.chars().map(char_width).sum()
+}
+
+impl Widget for Tabs {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let mut segments = Vec::with_capacity(self.titles.len() * 2);
+        for (i, title) in self.titles.iter().enumerate() {
+            if i > 0 {
+                segments.push((self.divider.clone(), self.style));
+            }
+            let style = if i == self.selected {
+                self.style.patch(self.highlight_style)
+            } else {
+                self.style
+            };
+            segments.push((format!(" {} ", title), style));
+        }
+
+        let total: u16 = segments.iter().map(|(s, _)| str_width(s)).sum();
+        let clipped = self.ellipsis && total > area.width;
+        let width = if clipped { area.width - 1 } else { area.width };
+
+        frame.render_area(Rect::new(area.x, area.y, width, 1), |f| {
+            let mut x = 0;
+            for (text, style) in &segments {
+                if x >= width {
+                    break;
+                }
+                f.write_str_with_style(x, 0, text, *style);
+                x = x.saturating_add(str_width(text));
+            }
+        });
+        if clipped {
+            frame.render_area(area, |f| {
+                f.write_str_with_style(width, 0, "\u{2026}", self.style);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, Color};
+
+    fn titles() -> Vec<String> {
+        vec!["One".to_string(), "Two".to_string(), "Three".to_string()]
+    }
+
+    fn row(buffer: &Buffer) -> String {
+        (0..buffer.width).map(|x| buffer.get(x, 0).symbol).collect()
+    }
+
+    #[test]
+    fn test_tabs_divider_and_highlight() {
+        let mut buffer = Buffer::new(20, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 20, 1));
+
+        Tabs::new(titles())
+            .select(1)
+            .highlight_style(Style::new().fg(Color::Yellow))
+            .render(Rect::new(0, 0, 20, 1), &mut frame);
+
+        assert_eq!(row(&buffer), " One \u{2502} Two \u{2502} Three  ");
+        assert_eq!(buffer.get(7, 0).style.foreground, Some(Color::Yellow));
+        assert_eq!(buffer.get(1, 0).style.foreground, None);
+        assert_eq!(buffer.get(5, 0).style.foreground, None);
+    }
+
+    #[test]
+    fn test_tabs_clipped_with_ellipsis() {
+        let mut buffer = Buffer::new(10, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+
+        Tabs::new(titles())
+            .divider("|")
+            .ellipsis(true)
+            .render(Rect::new(0, 0, 10, 1), &mut frame);
+
+        assert_eq!(row(&buffer), " One | Tw\u{2026}");
+    }
+}
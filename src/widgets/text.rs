@@ -1,30 +1,132 @@
 //! A simple widget that displays a string of text.
 
-use crate::{Frame, Rect, Style, widgets::Widget};
+use crate::{
+    Alignment, Frame, Modifier, Rect, Style, VerticalAlignment, buffer::char_width, widgets::Widget,
+};
+
+/// A run of text drawn with a single style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub content: String,
+    pub style: Style,
+}
+
+impl Span {
+    /// Creates a span with the default style.
+    pub fn raw<S: Into<String>>(content: S) -> Self {
+        Self::styled(content, Style::default())
+    }
+
+    /// Creates a span with the given style.
+    pub fn styled<S: Into<String>>(content: S, style: Style) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+}
+
+/// A line of [`Span`]s with an optional alignment of its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line {
+    pub spans: Vec<Span>,
+    pub alignment: Option<Alignment>,
+}
+
+impl Line {
+    /// Creates an unstyled line.
+    pub fn raw<S: Into<String>>(content: S) -> Self {
+        Self::from_spans(vec![Span::raw(content)])
+    }
+
+    /// Creates a line drawn entirely in `style`.
+    pub fn styled<S: Into<String>>(content: S, style: Style) -> Self {
+        Self::from_spans(vec![Span::styled(content, style)])
+    }
+
+    /// Creates a line from styled spans.
+    pub fn from_spans(spans: Vec<Span>) -> Self {
+        Self {
+            spans,
+            alignment: None,
+        }
+    }
+
+    /// Sets the alignment of this line, overriding the [`Text`] alignment.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+}
 
 /// A simple widget that displays a string of text.
 pub struct Text {
-    text: String,
+    lines: Vec<Line>,
     style: Style,
+    alignment: Alignment,
+    vertical_alignment: VerticalAlignment,
     wrap: bool,
+    truncate: bool,
 }
 
 impl Text {
     /// Creates a new text widget.
     pub fn new<S: Into<String>>(text: S) -> Self {
+        Self::from_spans(vec![Span::raw(text)])
+    }
+
+    /// Creates a text widget from a sequence of styled spans.
+    ///
+    /// Each span's style is layered on top of the widget [`style`](Self::style).
+    pub fn from_spans(spans: Vec<Span>) -> Self {
+        Self::from_lines(vec![Line::from_spans(spans)])
+    }
+
+    /// Creates a text widget from lines, each starting on a new row.
+    pub fn from_lines(lines: Vec<Line>) -> Self {
         Self {
-            text: text.into(),
+            lines,
             style: Style::default(),
+            alignment: Alignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
             wrap: false,
+            truncate: false,
         }
     }
 
+    /// Creates a text widget from lightweight markup.
+    ///
+    /// Supported markers:
+    /// * `*bold*`
+    /// * `_italic_`
+    /// * `` `code` `` (drawn reversed; markers inside are literal)
+    ///
+    /// A backslash makes the next character literal, so `\*` draws an asterisk.
+    pub fn markup(s: &str) -> Self {
+        Self::from_spans(parse_markup(s))
+    }
+
     /// Sets the style of the text.
     pub fn style(mut self, style: Style) -> Self {
         self.style = style;
         self
     }
 
+    /// Sets the alignment of lines that don't set their own (default: left).
+    pub fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets where the rows sit within the area's height (default: top).
+    ///
+    /// All rows move together as a block; rows that don't fit are cut from
+    /// the bottom.
+    pub fn vertical_align(mut self, alignment: VerticalAlignment) -> Self {
+        self.vertical_alignment = alignment;
+        self
+    }
+
     /// Sets whether the text should wrap when it reaches the edge of the area.
     ///
     /// If true, text will wrap to the next line. If false (default), text will be clipped.
@@ -32,41 +134,177 @@ impl Text {
         self.wrap = wrapped;
         self
     }
+
+    /// Sets whether rows wider than the area end in `…` instead of being cut
+    /// off silently (default: false).
+    ///
+    /// The ellipsis takes the place of the last visible character. With
+    /// [`wrap`](Self::wrap), it only applies to words too long for a row.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.truncate = truncate;
+        self
+    }
+}
+
+/// Splits markup into spans, toggling styles at each unescaped marker.
+fn parse_markup(s: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    // Active markers, in the order `*`, `_`, `` ` ``.
+    let mut active = [false; 3];
+    const MODIFIERS: [Modifier; 3] = [Modifier::BOLD, Modifier::ITALIC, Modifier::REVERSED];
+
+    let flush = |current: &mut String, spans: &mut Vec<Span>, active: &[bool; 3]| {
+        if current.is_empty() {
+            return;
+        }
+        let style = MODIFIERS
+            .iter()
+            .zip(active)
+            .filter(|(_, on)| **on)
+            .fold(Style::new(), |style, (m, _)| style.modifier(*m));
+        spans.push(Span::styled(std::mem::take(current), style));
+    };
+
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        let code = active[2];
+        let marker = match c {
+            '\\' => {
+                current.extend(chars.next());
+                continue;
+            }
+            '*' if !code => 0,
+            '_' if !code => 1,
+            '`' => 2,
+            _ => {
+                current.push(c);
+                continue;
+            }
+        };
+
+        flush(&mut current, &mut spans, &active);
+        active[marker] = !active[marker];
+    }
+
+    flush(&mut current, &mut spans, &active);
+    spans
+}
+
+pub(crate) type StyledChar = (char, Style);
+
+/// Returns the number of terminal columns `chars` occupies.
+pub(crate) fn columns(chars: &[StyledChar]) -> usize {
+    chars.iter().map(|&(c, _)| char_width(c) as usize).sum()
+}
+
+/// Draws `row` starting at (`x`, `y`), advancing by each char's width.
+///
+/// The frame clips whatever runs past its right edge.
+pub(crate) fn draw_row(frame: &mut Frame, x: u16, y: u16, row: &[StyledChar]) {
+    let mut col = x;
+    let mut buf = [0u8; 4];
+    for &(c, style) in row {
+        frame.write_str_with_style(col, y, c.encode_utf8(&mut buf), style);
+        col = col.saturating_add(char_width(c));
+    }
+}
+
+/// Breaks a paragraph into rows at most `width` wide, splitting on whitespace.
+///
+/// Words are joined by a single space; a word longer than `width` gets a row
+/// of its own and is clipped when drawn.
+fn wrap_words(paragraph: &[StyledChar], width: usize) -> Vec<Vec<StyledChar>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<StyledChar> = Vec::new();
+
+    for word in paragraph
+        .split(|(c, _)| c.is_whitespace())
+        .filter(|w| !w.is_empty())
+    {
+        if !row.is_empty() && columns(&row) + 1 + columns(word) > width {
+            rows.push(std::mem::take(&mut row));
+        }
+        if !row.is_empty() {
+            // The separator takes the style of the character before it.
+            let style = row[row.len() - 1].1;
+            row.push((' ', style));
+        }
+        row.extend_from_slice(word);
+    }
+
+    rows.push(row);
+    rows
 }
 
 impl Widget for Text {
     fn render(self, area: Rect, frame: &mut Frame) {
-        frame.render_area(area, |f| {
-            f.with_style(self.style, |f| {
+        // Flatten each line into styled characters so wrapping can split words
+        // regardless of where span boundaries fall. Embedded newlines start a
+        // new row with the same alignment.
+        let width = area.width as usize;
+        let mut rows: Vec<(Vec<StyledChar>, Alignment)> = Vec::new();
+        for line in &self.lines {
+            let alignment = line.alignment.unwrap_or(self.alignment);
+            let chars: Vec<StyledChar> = line
+                .spans
+                .iter()
+                .flat_map(|span| {
+                    let style = self.style.patch(span.style);
+                    span.content.chars().map(move |c| (c, style))
+                })
+                .collect();
+
+            for paragraph in chars.split(|(c, _)| *c == '\n') {
                 if self.wrap {
-                    let mut wx: u16 = 0;
-                    let mut wy: u16 = 0;
-
-                    for line in self.text.lines() {
-                        for w in line.split_whitespace() {
-                            if wx + w.len() as u16 > f.width() {
-                                wx = 0;
-                                wy += 1;
-                            }
-                            if wy >= f.height() {
-                                break;
-                            }
-
-                            f.write_str(wx, wy, w);
-                            wx += w.len() as u16 + 1;
-                        }
-
-                        // End of paragraph: force new line
-                        wx = 0;
-                        wy += 1;
-                        if wy >= f.height() {
-                            break;
-                        }
-                    }
+                    rows.extend(
+                        wrap_words(paragraph, width)
+                            .into_iter()
+                            .map(|r| (r, alignment)),
+                    );
                 } else {
-                    f.write_str(0, 0, &self.text);
+                    rows.push((paragraph.to_vec(), alignment));
                 }
-            });
+            }
+        }
+
+        if self.truncate && width > 0 {
+            for (row, _) in rows.iter_mut().filter(|(row, _)| columns(row) > width) {
+                // Keep what fits in one column less than the width, then
+                // mark the cut with an ellipsis in the cut char's style.
+                let mut used = 0;
+                let keep = row
+                    .iter()
+                    .take_while(|&&(c, _)| {
+                        used += char_width(c) as usize;
+                        used < width
+                    })
+                    .count();
+                let style = row[keep].1;
+                row.truncate(keep);
+                row.push(('\u{2026}', style));
+            }
+        }
+
+        let used = rows.len().min(area.height as usize) as u16;
+        let top = match self.vertical_alignment {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Center => (area.height - used) / 2,
+            VerticalAlignment::Bottom => area.height - used,
+        };
+
+        frame.render_area(area, |f| {
+            for (row_index, (row, alignment)) in rows.iter().enumerate().take(used as usize) {
+                let y = top as usize + row_index;
+                let len = columns(row).min(width) as u16;
+                let x = match alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => (f.width() - len) / 2,
+                    Alignment::Right => f.width() - len,
+                };
+
+                draw_row(f, x, y as u16, row);
+            }
         });
     }
 }
@@ -76,6 +314,64 @@ mod tests {
     use super::*;
     use crate::{Buffer, Color};
 
+    fn render_row(text: Text, width: u16) -> String {
+        let mut buffer = Buffer::new(width, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, width, 1));
+        text.render(Rect::new(0, 0, width, 1), &mut frame);
+        buffer.to_text(false)
+    }
+
+    #[test]
+    fn test_text_align_center_and_right() {
+        assert_eq!(
+            render_row(Text::new("abcd").align(Alignment::Center), 10),
+            "   abcd   "
+        );
+        assert_eq!(
+            render_row(Text::new("abcd").align(Alignment::Right), 10),
+            "      abcd"
+        );
+    }
+
+    #[test]
+    fn test_text_vertical_center_block() {
+        let mut buffer = Buffer::new(3, 5);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 5));
+        Text::new("ab\nc")
+            .vertical_align(VerticalAlignment::Center)
+            .render(Rect::new(0, 0, 3, 5), &mut frame);
+        assert_eq!(buffer.to_text(true), "\nab\nc\n\n");
+
+        let mut buffer = Buffer::new(3, 5);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 3, 5));
+        Text::new("ab\nc")
+            .vertical_align(VerticalAlignment::Bottom)
+            .align(Alignment::Right)
+            .render(Rect::new(0, 0, 3, 5), &mut frame);
+        assert_eq!(buffer.to_text(true), "\n\n\n ab\n  c");
+    }
+
+    #[test]
+    fn test_text_truncate_fits_exactly() {
+        assert_eq!(render_row(Text::new("Hello").truncate(true), 5), "Hello");
+    }
+
+    #[test]
+    fn test_text_truncate_one_over() {
+        assert_eq!(
+            render_row(Text::new("Hello!").truncate(true), 5),
+            "Hell\u{2026}"
+        );
+        // Without truncation the row is just clipped.
+        assert_eq!(render_row(Text::new("Hello!"), 5), "Hello");
+    }
+
+    #[test]
+    fn test_text_truncate_far_over() {
+        let text = Text::new("A very long line indeed").truncate(true);
+        assert_eq!(render_row(text, 8), "A very \u{2026}");
+    }
+
     #[test]
     fn test_text_render() {
         let mut buffer = Buffer::new(10, 1);
@@ -101,6 +397,89 @@ mod tests {
         assert_eq!(buffer.get(0, 0).style.foreground, Some(Color::Red));
     }
 
+    #[test]
+    fn test_text_markup_bold() {
+        let mut buffer = Buffer::new(10, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+
+        Text::markup("*hi* there").render(Rect::new(0, 0, 10, 1), &mut frame);
+
+        assert_eq!(buffer.get(0, 0).symbol, 'h');
+        assert_eq!(buffer.get(1, 0).symbol, 'i');
+        assert_eq!(buffer.get(2, 0).symbol, ' ');
+        assert!(buffer.get(0, 0).style.modifiers.contains(Modifier::BOLD));
+        assert!(!buffer.get(3, 0).style.modifiers.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_text_markup_escapes_and_code() {
+        let spans = parse_markup("\\*a `_b_`");
+
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0], Span::raw("*a "));
+        assert_eq!(spans[1].content, "_b_");
+        assert!(spans[1].style.modifiers.contains(Modifier::REVERSED));
+        assert!(!spans[1].style.modifiers.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_text_lines_keep_their_styles() {
+        let mut buffer = Buffer::new(6, 3);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 3));
+        let text = Text::from_lines(vec![
+            Line::raw("ok"),
+            Line::styled("error!", Style::new().fg(Color::Red)),
+            Line::from_spans(vec![Span::raw("a "), Span::styled("b", Style::bold())]),
+        ])
+        .style(Style::new().bg(Color::Black));
+
+        text.render(Rect::new(0, 0, 6, 3), &mut frame);
+
+        assert_eq!(buffer.to_text(true), "ok\nerror!\na b");
+        assert_eq!(buffer.get(0, 0).style, Style::new().bg(Color::Black));
+        assert_eq!(
+            buffer.get(5, 1).style,
+            Style::new().fg(Color::Red).bg(Color::Black)
+        );
+        assert_eq!(buffer.get(0, 2).style.modifiers, Modifier::empty());
+        assert!(buffer.get(2, 2).style.modifiers.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_text_per_line_alignment() {
+        let mut buffer = Buffer::new(10, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 2));
+        let text = Text::from_lines(vec![
+            Line::raw("Title").alignment(Alignment::Center),
+            Line::raw("Body"),
+        ]);
+
+        text.render(Rect::new(0, 0, 10, 2), &mut frame);
+
+        // "Title" centered in 10 -> starts at x=2
+        assert_eq!(buffer.get(1, 0).symbol, ' ');
+        assert_eq!(buffer.get(2, 0).symbol, 'T');
+        // "Body" falls back to the text alignment (left)
+        assert_eq!(buffer.get(0, 1).symbol, 'B');
+    }
+
+    #[test]
+    fn test_text_align_right_with_override() {
+        let mut buffer = Buffer::new(6, 2);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 6, 2));
+        let text = Text::from_lines(vec![
+            Line::raw("ab"),
+            Line::raw("cd").alignment(Alignment::Left),
+        ])
+        .align(Alignment::Right);
+
+        text.render(Rect::new(0, 0, 6, 2), &mut frame);
+
+        assert_eq!(buffer.get(4, 0).symbol, 'a');
+        assert_eq!(buffer.get(5, 0).symbol, 'b');
+        assert_eq!(buffer.get(0, 1).symbol, 'c');
+    }
+
     #[test]
     fn test_text_wrap() {
         let mut buffer = Buffer::new(5, 3);
@@ -117,4 +496,18 @@ mod tests {
         assert_eq!(buffer.get(0, 1).symbol, 'W');
         assert_eq!(buffer.get(4, 1).symbol, 'd');
     }
+
+    #[test]
+    fn test_text_wide_chars_take_two_columns() {
+        assert_eq!(render_row(Text::new("世界ab"), 10), "世界ab    ");
+        assert_eq!(
+            render_row(Text::new("世界").align(Alignment::Right), 6),
+            "  世界"
+        );
+        assert_eq!(
+            render_row(Text::new("世界").align(Alignment::Center), 8),
+            "  世界  "
+        );
+        assert_eq!(render_row(Text::new("世界世界").truncate(true), 5), "世界…");
+    }
 }
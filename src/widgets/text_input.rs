@@ -0,0 +1,256 @@
+//! A single-line editable text field.
+
+use crate::{Frame, Rect, Style, buffer::char_width, widgets::Widget};
+
+/// The text and caret of a [`TextInput`], kept by the application across
+/// frames.
+///
+/// The caret is a char index into the value, from `0` (before the first
+/// char) to the number of chars (after the last one), so editing never
+/// splits a multi-byte character.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInputState {
+    value: String,
+    cursor: usize,
+    offset: usize,
+}
+
+impl TextInputState {
+    /// Creates an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a state holding `value`, with the caret at the end.
+    pub fn with_value<S: Into<String>>(value: S) -> Self {
+        let value = value.into();
+        Self {
+            cursor: value.chars().count(),
+            value,
+            offset: 0,
+        }
+    }
+
+    /// Returns the current text.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the caret position as a char index.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Inserts a character at the caret and moves the caret past it.
+    pub fn insert_char(&mut self, c: char) {
+        let at = self.byte_index(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+    }
+
+    /// Deletes the character before the caret (Backspace).
+    pub fn delete_backward(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.value.remove(self.byte_index(self.cursor));
+        }
+    }
+
+    /// Deletes the character after the caret (Delete).
+    pub fn delete_forward(&mut self) {
+        if self.cursor < self.len() {
+            self.value.remove(self.byte_index(self.cursor));
+        }
+    }
+
+    /// Moves the caret one character to the left.
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Moves the caret one character to the right.
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.len());
+    }
+
+    /// Moves the caret to the start of the text.
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Moves the caret to the end of the text.
+    pub fn move_end(&mut self) {
+        self.cursor = self.len();
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    /// Converts a char index into a byte offset into `value`.
+    fn byte_index(&self, index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+}
+
+/// A single-line text field that draws a [`TextInputState`] and places the
+/// terminal cursor at its caret.
+///
+/// Text wider than the area scrolls horizontally to keep the caret visible.
+pub struct TextInput<'a> {
+    state: &'a mut TextInputState,
+    style: Style,
+}
+
+impl<'a> TextInput<'a> {
+    /// Creates a text field that renders from and updates `state`.
+    ///
+    /// As with [`List::state`](crate::widgets::List::state), keep the state in
+    /// a `RefCell` in the model, since [`Application::draw`] only gets `&self`.
+    ///
+    /// [`Application::draw`]: crate::Application::draw
+    pub fn new(state: &'a mut TextInputState) -> Self {
+        Self {
+            state,
+            style: Style::default(),
+        }
+    }
+
+    /// Sets the style of the text.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for TextInput<'_> {
+    fn render(self, area: Rect, frame: &mut Frame) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        // Scroll just enough to keep the caret, which may sit one past the
+        // last char, inside the area. Offsets are char indices, but the room
+        // they take is measured in columns.
+        let width = area.width as usize;
+        let state = self.state;
+        state.cursor = state.cursor.min(state.len());
+        let widths: Vec<usize> = state
+            .value
+            .chars()
+            .map(|c| char_width(c) as usize)
+            .collect();
+        let columns = |from: usize, to: usize| widths[from..to].iter().sum::<usize>();
+        let caret_width = widths.get(state.cursor).copied().unwrap_or(1);
+        if state.cursor < state.offset {
+            state.offset = state.cursor;
+        }
+        while state.offset < state.cursor
+            && columns(state.offset, state.cursor) + caret_width > width
+        {
+            state.offset += 1;
+        }
+
+        let mut used = 0;
+        let visible: String = state
+            .value
+            .chars()
+            .zip(&widths)
+            .skip(state.offset)
+            .take_while(|&(_, w)| {
+                used += w;
+                used <= width
+            })
+            .map(|(c, _)| c)
+            .collect();
+        let caret = columns(state.offset, state.cursor) as u16;
+        frame.with_style(self.style, |f| {
+            f.render_area(area, |f| {
+                f.write_str(0, 0, &visible);
+                f.set_cursor(caret, 0);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buffer;
+
+    #[test]
+    fn test_text_input_editing() {
+        let mut state = TextInputState::with_value("ac");
+        state.move_left();
+        state.insert_char('b');
+        assert_eq!(state.value(), "abc");
+        assert_eq!(state.cursor(), 2);
+
+        state.delete_backward();
+        assert_eq!(state.value(), "ac");
+        state.delete_forward();
+        assert_eq!(state.value(), "a");
+        state.delete_forward();
+        assert_eq!(state.value(), "a");
+
+        state.move_home();
+        state.delete_backward();
+        assert_eq!(state.cursor(), 0);
+        state.move_end();
+        assert_eq!(state.cursor(), 1);
+        state.move_right();
+        assert_eq!(state.cursor(), 1);
+    }
+
+    #[test]
+    fn test_text_input_multibyte() {
+        let mut state = TextInputState::with_value("héllo");
+        state.move_home();
+        state.move_right();
+        state.delete_forward();
+        assert_eq!(state.value(), "hllo");
+
+        state.insert_char('é');
+        state.insert_char('日');
+        assert_eq!(state.value(), "hé日llo");
+        state.delete_backward();
+        assert_eq!(state.value(), "héllo");
+        assert_eq!(state.cursor(), 2);
+    }
+
+    #[test]
+    fn test_text_input_scrolls_to_cursor() {
+        let mut buffer = Buffer::new(10, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 10, 1));
+        let mut state = TextInputState::with_value("abcdefgh");
+
+        TextInput::new(&mut state).render(Rect::new(2, 0, 5, 1), &mut frame);
+        assert_eq!(frame.cursor(), Some((6, 0)));
+
+        // The caret sits after "h", so the last 4 chars and the caret fit.
+        let row: String = (2..7).map(|x| buffer.get(x, 0).symbol).collect();
+        assert_eq!(row, "efgh ");
+        assert_eq!(buffer.get(7, 0).symbol, ' ');
+    }
+
+    #[test]
+    fn test_text_input_wide_chars() {
+        let mut buffer = Buffer::new(5, 1);
+        let mut frame = Frame::new(&mut buffer, Rect::new(0, 0, 5, 1));
+        let mut state = TextInputState::with_value("日本");
+
+        TextInput::new(&mut state).render(Rect::new(0, 0, 5, 1), &mut frame);
+        // Each char takes two columns, so the caret after them is at 4.
+        assert_eq!(frame.cursor(), Some((4, 0)));
+
+        // Scrolling keeps the caret inside the area, counting columns.
+        let mut state = TextInputState::with_value("日本語");
+        TextInput::new(&mut state).render(Rect::new(0, 0, 5, 1), &mut frame);
+        assert_eq!(frame.cursor(), Some((4, 0)));
+        assert_eq!(buffer.get(0, 0).symbol, '本');
+        assert_eq!(buffer.get(2, 0).symbol, '語');
+    }
+}